@@ -0,0 +1,30 @@
+//! A `conrod::clipboard::Clipboard` implementation that shares its contents with the system
+//! clipboard via the `clipboard` crate.
+
+extern crate clipboard;
+
+use self::clipboard::ClipboardContext;
+
+/// The system clipboard.
+pub struct System {
+    context: ClipboardContext,
+}
+
+impl System {
+    /// Connect to the system clipboard.
+    ///
+    /// Returns `None` if the platform's clipboard could not be accessed.
+    pub fn new() -> Option<Self> {
+        ClipboardContext::new().ok().map(|context| System { context: context })
+    }
+}
+
+impl ::clipboard::Clipboard for System {
+    fn contents(&mut self) -> Option<String> {
+        self.context.get_contents().ok()
+    }
+    fn set_contents(&mut self, contents: String) {
+        // There's not much we can do if the system clipboard refuses the contents.
+        let _ = self.context.set_contents(contents);
+    }
+}
@@ -1,10 +1,12 @@
-//! A function for converting a `glutin::Event` to a `conrod::event::Raw`.
+//! Functions for converting a `glutin::Event` to a `conrod::event::Raw` and for applying the
+//! `Ui`'s requested mouse cursor icon to a `glutin::Window`.
 //!
 //! The following is adapted from the piston `glutin_window` crate.
 
 extern crate glutin;
 
 use Scalar;
+use cursor;
 use event::{self, Input, Motion};
 use input;
 
@@ -224,3 +226,23 @@ pub fn map_mouse(mouse_button: glutin::MouseButton) -> input::MouseButton {
         _ => MouseButton::Unknown
     }
 }
+
+/// Maps conrod's mouse cursor icon to Glutin's.
+pub fn map_mouse_cursor(cursor: cursor::MouseCursor) -> glutin::MouseCursor {
+    use cursor::MouseCursor;
+    match cursor {
+        MouseCursor::Arrow => glutin::MouseCursor::Default,
+        MouseCursor::Text => glutin::MouseCursor::Text,
+        MouseCursor::Hand => glutin::MouseCursor::Hand,
+        MouseCursor::ResizeHorizontal => glutin::MouseCursor::EwResize,
+        MouseCursor::ResizeVertical => glutin::MouseCursor::NsResize,
+        MouseCursor::ResizeTopLeftBottomRight => glutin::MouseCursor::NwseResize,
+        MouseCursor::Grab => glutin::MouseCursor::Grab,
+        MouseCursor::NotAllowed => glutin::MouseCursor::NotAllowed,
+    }
+}
+
+/// Apply the given mouse cursor icon to the given window, i.e. the result of `Ui::mouse_cursor`.
+pub fn set_mouse_cursor(window: &glutin::Window, cursor: cursor::MouseCursor) {
+    window.set_cursor(map_mouse_cursor(cursor));
+}
@@ -7,6 +7,7 @@
 //! If there is a popular backend that you would like to see support for that is currently missing
 //! from this module, feel free to open an issue or pull request at the conrod repository.
 
+#[cfg(feature="clipboard")] pub mod clipboard;
 #[cfg(feature="glium")] pub mod glium;
 #[cfg(feature="glutin")] pub mod glutin;
 #[cfg(feature="piston")] pub mod piston;
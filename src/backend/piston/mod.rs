@@ -1,4 +1,8 @@
 //! Functionality for simplifying the work involved when using conrod along-side piston.
+//!
+//! Piston's `Window` trait provides no way of setting the mouse cursor icon. When using the default
+//! `GlutinWindow`, enable the `glutin` feature and apply `Ui::mouse_cursor` to its inner window
+//! via `conrod::backend::glutin::set_mouse_cursor`.
 
 pub mod draw;
 pub mod event;
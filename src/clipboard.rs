@@ -0,0 +1,41 @@
+//! Types and functionality related to the clipboard used by text widgets for copy, cut and paste.
+//!
+//! The `Ui` owns a single `Clipboard` which is shared between all widgets. By default this is a
+//! `Memory` clipboard that only lives as long as the `Ui`. To share the clipboard with the rest
+//! of the system, a backend-specific implementation may be given via `UiBuilder::clipboard` (see
+//! the `backend::clipboard` module).
+
+/// A place to which text may be copied and from which text may be pasted.
+pub trait Clipboard {
+    /// The current contents of the clipboard.
+    ///
+    /// Returns `None` if the clipboard is empty or if its contents could not be retrieved.
+    fn contents(&mut self) -> Option<String>;
+    /// Replace the contents of the clipboard with the given `String`.
+    fn set_contents(&mut self, contents: String);
+}
+
+/// A simple in-memory `Clipboard`.
+///
+/// This is the default `Clipboard` used by the `Ui`. Its contents are only accessible within the
+/// `Ui` that owns it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Memory {
+    contents: Option<String>,
+}
+
+impl Memory {
+    /// An empty in-memory `Clipboard`.
+    pub fn new() -> Self {
+        Memory { contents: None }
+    }
+}
+
+impl Clipboard for Memory {
+    fn contents(&mut self) -> Option<String> {
+        self.contents.clone()
+    }
+    fn set_contents(&mut self, contents: String) {
+        self.contents = Some(contents);
+    }
+}
@@ -0,0 +1,32 @@
+//! The icons that widgets may request for the mouse cursor.
+//!
+//! Widgets request an icon during `update` via `UiCell::set_mouse_cursor`. The application may
+//! then retrieve the icon for the widget under (or capturing) the mouse via `Ui::mouse_cursor` and
+//! apply it to its window, i.e. via `backend::glutin::map_mouse_cursor`.
+
+/// The icon displayed for the mouse cursor.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MouseCursor {
+    /// The default arrow.
+    Arrow,
+    /// The I-beam used over editable text.
+    Text,
+    /// The pointing hand used over links and buttons.
+    Hand,
+    /// A horizontal resize arrow, i.e. for dragging a vertical edge left or right.
+    ResizeHorizontal,
+    /// A vertical resize arrow, i.e. for dragging a horizontal edge up or down.
+    ResizeVertical,
+    /// A diagonal resize arrow from the top left to the bottom right.
+    ResizeTopLeftBottomRight,
+    /// An open hand, used for something that may be grabbed and dragged.
+    Grab,
+    /// Indicates that the action under the cursor is not allowed.
+    NotAllowed,
+}
+
+impl Default for MouseCursor {
+    fn default() -> Self {
+        MouseCursor::Arrow
+    }
+}
@@ -70,6 +70,35 @@ pub use piston_input::Event as RawEvent;
 pub use piston_input::{Input, Motion};
 
 
+/// Input method (IME) composition events.
+///
+/// The `pistoncore-input` `Input` type has no notion of text composition, so these are instead
+/// given to the `Ui` via the `Ui::handle_composition` method. Backends should call this as the
+/// platform's input method begins, updates and ends the composition of some text. The `Ui`
+/// delivers each event to the widget capturing the keyboard.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Composition {
+    /// The input method has begun composing text.
+    Start,
+    /// The text currently being composed (the "pre-edit") has changed.
+    Update(PreEdit),
+    /// The composition has ended, producing the given text.
+    ///
+    /// The `Ui` also delivers the committed text as a `Text` event, so backends need not produce
+    /// a separate `Input::Text` for it. A cancelled composition should commit an empty string.
+    Commit(String),
+}
+
+/// The text being composed by an input method, prior to being committed.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PreEdit {
+    /// The text being composed.
+    pub string: String,
+    /// The char index of the input method's cursor within the `string`, if it has one.
+    pub cursor: Option<usize>,
+}
+
+
 /// Enum containing all the events that the `Ui` may provide.
 #[derive(Clone, PartialEq, Debug)]
 pub enum Event {
@@ -86,6 +115,9 @@ pub enum Event {
 pub enum Ui {
     /// Entered text, along with the widget that was capturing the keyboard at the time.
     Text(Option<widget::Index>, Text),
+    /// Input method composition, along with the widget that was capturing the keyboard at the
+    /// time.
+    Composition(Option<widget::Index>, Composition),
     /// Some button was pressed, along with the widget that was capturing the device whose button
     /// which was pressed.
     Press(Option<widget::Index>, Press),
@@ -103,8 +135,18 @@ pub enum Ui {
     /// Two `Click` events with the same `button` and `xy` occurring within a duration that is less
     /// that the `theme.double_click_threshold`.
     DoubleClick(Option<widget::Index>, DoubleClick),
+    /// Two or more consecutive `Click` events, each with the same `button` and `xy` and occurring
+    /// within the `theme.double_click_threshold` of the last.
+    ///
+    /// This is produced alongside each `DoubleClick`, as well as for each following `Click`.
+    MultiClick(Option<widget::Index>, MultiClick),
     /// Represents a pointing device button being pressed and a subsequent movement of the mouse.
     Drag(Option<widget::Index>, Drag),
+    /// The `Drag` of a pointing device button was cancelled, i.e. by pressing `Escape`, along with
+    /// the widget that was under the mouse when the button was pressed.
+    ///
+    /// The button is considered released, so no `Click` follows when it is physically released.
+    DragCancelled(Option<widget::Index>, DragCancelled),
     /// A generic scroll event.
     ///
     /// `Scroll` does not necessarily have to get created by a mouse wheel, it could be generated
@@ -114,6 +156,45 @@ pub enum Ui {
     /// top to bottom. The remainder will then be applied to either 1. whatever widget captures the
     /// device from which the scroll was emitted or 2. whatever widget was specified.
     Scroll(Option<widget::Index>, Scroll),
+    /// Momentum given to the scrolling of the given widget, i.e. when a finger that was panning
+    /// across a scrollable widget is lifted.
+    Fling(Option<widget::Index>, Fling),
+    /// A finger touched, moved across or was lifted from the screen, along with the widget that was
+    /// under the finger when it first touched the screen.
+    Touch(Option<widget::Index>, Touch),
+    /// A finger briefly touched the screen and was lifted without moving.
+    Tap(Option<widget::Index>, Tap),
+    /// A finger was held upon the screen without moving for the `theme.long_press_duration`.
+    LongPress(Option<widget::Index>, LongPress),
+    /// A single finger moved across the screen further than the `theme.touch_drag_threshold`.
+    Pan(Option<widget::Index>, Pan),
+    /// Two fingers touching the same widget moved towards or away from each other.
+    Pinch(Option<widget::Index>, Pinch),
+    /// Two fingers touching the same widget rotated around each other.
+    Rotate(Option<widget::Index>, Rotate),
+    /// The mouse rested over the given widget for the `theme.hover_delay` without moving.
+    Hover(Option<widget::Index>, Hover),
+    /// Indicates that the mouse has moved over the given widget.
+    MouseEnter(widget::Index),
+    /// Indicates that the mouse is no longer over the given widget.
+    MouseLeave(widget::Index),
+    /// The payload of a drag-and-drop was dragged onto the given widget.
+    DragEnter(widget::Index, DragAndDrop),
+    /// The payload of a drag-and-drop was dragged over the given widget.
+    DragOver(widget::Index, DragAndDrop),
+    /// The payload of a drag-and-drop was dragged off of the given widget.
+    DragLeave(widget::Index, DragAndDrop),
+    /// The payload of a drag-and-drop was dropped onto the given widget.
+    ///
+    /// The widget may accept the payload using `UiCell::accept_drop`.
+    Drop(widget::Index, DragAndDrop),
+    /// The drag-and-drop started by the given widget has ended.
+    DragEnd(widget::Index, DragEnd),
+    /// The registered keyboard shortcut with the given `Id` was triggered.
+    Shortcut(input::shortcut::Id),
+    /// A controller stick requested that the value of the widget capturing the keyboard be
+    /// adjusted.
+    Adjust(Option<widget::Index>, Adjust),
     /// Indicates that the given widget has captured the mouse.
     WidgetCapturesMouse(widget::Index),
     /// Indicates that the given widget has released the mouse from capturing.
@@ -135,6 +216,8 @@ pub enum Ui {
 pub enum Widget {
     /// Entered text.
     Text(Text),
+    /// Input method composition.
+    Composition(Composition),
     /// Represents all forms of motion input.
     Move(Move),
     /// Some button was pressed.
@@ -147,10 +230,53 @@ pub enum Widget {
     /// Two `Click` events with the same `button` and `xy` occurring within a duration that is less
     /// that the `theme.double_click_threshold`.
     DoubleClick(DoubleClick),
+    /// Two or more consecutive `Click` events, each with the same `button` and `xy` and occurring
+    /// within the `theme.double_click_threshold` of the last.
+    MultiClick(MultiClick),
     /// Represents a pointing device button being pressed and a subsequent movement of the mouse.
     Drag(Drag),
+    /// The `Drag` of a pointing device button was cancelled.
+    ///
+    /// Widgets that change their value while dragged should restore their pre-drag value.
+    DragCancelled(DragCancelled),
     /// Represents the amount of scroll that has been applied to this widget.
     Scroll(Scroll),
+    /// Momentum given to the scrolling of this widget.
+    Fling(Fling),
+    /// A finger touched, moved across or was lifted from the screen.
+    Touch(Touch),
+    /// A finger briefly touched the screen and was lifted without moving.
+    Tap(Tap),
+    /// A finger was held upon the screen without moving for the `theme.long_press_duration`.
+    LongPress(LongPress),
+    /// A single finger moved across the screen further than the `theme.touch_drag_threshold`.
+    Pan(Pan),
+    /// Two fingers touching the widget moved towards or away from each other.
+    Pinch(Pinch),
+    /// Two fingers touching the widget rotated around each other.
+    Rotate(Rotate),
+    /// The mouse rested over the widget for the `theme.hover_delay` without moving.
+    Hover(Hover),
+    /// The mouse has moved over the widget.
+    MouseEnter,
+    /// The mouse is no longer over the widget.
+    MouseLeave,
+    /// The payload of a drag-and-drop was dragged onto the widget.
+    DragEnter(DragAndDrop),
+    /// The payload of a drag-and-drop was dragged over the widget.
+    DragOver(DragAndDrop),
+    /// The payload of a drag-and-drop was dragged off of the widget.
+    DragLeave(DragAndDrop),
+    /// The payload of a drag-and-drop was dropped onto the widget.
+    ///
+    /// The widget may accept the payload using `UiCell::accept_drop`.
+    Drop(DragAndDrop),
+    /// The drag-and-drop started by the widget has ended.
+    DragEnd(DragEnd),
+    /// A controller stick requested that the widget's value be adjusted.
+    Adjust(Adjust),
+    /// An event that was not handled by one of the widget's descendants.
+    Bubble(Bubble),
     /// The window's dimensions were resized.
     WindowResized(Dimensions),
     /// The widget has captured the mouse.
@@ -271,6 +397,15 @@ pub struct Drag {
     pub modifiers: input::keyboard::ModifierKey,
 }
 
+/// Contains all the relevant information for a cancelled mouse drag.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DragCancelled {
+    /// The mouse button that was being held during the drag.
+    pub button: input::MouseButton,
+    /// Which modifier keys were being held when the drag was cancelled.
+    pub modifiers: input::keyboard::ModifierKey,
+}
+
 /// Contains all the relevant information for a mouse click.
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Click {
@@ -295,6 +430,21 @@ pub struct DoubleClick {
     pub modifiers: input::keyboard::ModifierKey,
 }
 
+/// Contains all the relevant information for a click that follows one or more consecutive clicks.
+///
+/// When handling this event, be sure to check that you are handling the intended `button` too.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MultiClick {
+    /// Which mouse button was clicked
+    pub button: input::MouseButton,
+    /// The position at which the mouse was released.
+    pub xy: Point,
+    /// Which modifier keys, if any, that were being held down when the user clicked
+    pub modifiers: input::keyboard::ModifierKey,
+    /// The number of consecutive clicks, i.e. `2` for a double click and `3` for a triple click.
+    pub count: u32,
+}
+
 /// Holds all the relevant information about a scroll event
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Scroll {
@@ -306,6 +456,131 @@ pub struct Scroll {
     pub modifiers: input::keyboard::ModifierKey,
 }
 
+/// Contains all relevant information for a `Fling` event.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Fling {
+    /// The velocity given to the scroll offset along each axis in points per second.
+    pub velocity: [Scalar; 2],
+}
+
+/// Contains all relevant information for a hover.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Hover {
+    /// The position at which the mouse is resting.
+    pub xy: Point,
+    /// Which modifier keys, if any, were being held down at the time.
+    pub modifiers: input::keyboard::ModifierKey,
+}
+
+/// Contains all relevant information for a touch event.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Touch {
+    /// Whether the finger started touching, moved across or stopped touching the screen.
+    pub phase: input::Touch,
+    /// The unique identifier of the finger.
+    pub id: input::state::touch::Id,
+    /// The position of the finger.
+    pub xy: Point,
+}
+
+/// Contains all relevant information for a tap.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Tap {
+    /// The unique identifier of the finger that tapped.
+    pub id: input::state::touch::Id,
+    /// The position at which the finger was lifted.
+    pub xy: Point,
+}
+
+/// Contains all relevant information for a long press.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct LongPress {
+    /// The unique identifier of the finger that is pressing.
+    pub id: input::state::touch::Id,
+    /// The position of the finger.
+    pub xy: Point,
+}
+
+/// Contains all relevant information for a single finger pan.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Pan {
+    /// The unique identifier of the finger that is panning.
+    pub id: input::state::touch::Id,
+    /// The point at which the finger first touched the screen.
+    pub origin: Point,
+    /// The point from which this pan event began.
+    pub from: Point,
+    /// The point at which this pan event ended.
+    pub to: Point,
+    /// The magnitude of the vector between `from` and `to`.
+    pub delta_xy: Point,
+    /// The magnitude of the vector between `origin` and `to`.
+    pub total_delta_xy: Point,
+}
+
+/// Contains all relevant information for a two finger pinch.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Pinch {
+    /// The point midway between the two fingers.
+    pub centre: Point,
+    /// The ratio between the new and the previous distance between the fingers.
+    ///
+    /// A value greater than `1.0` indicates the fingers moving apart (i.e. zooming in).
+    pub scale: Scalar,
+}
+
+/// Contains all relevant information for a two finger rotation.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Rotate {
+    /// The point midway between the two fingers.
+    pub centre: Point,
+    /// The angle in radians by which the fingers rotated, counter-clockwise being positive.
+    pub radians: Scalar,
+}
+
+/// Contains all relevant information for an event produced by a drag-and-drop.
+///
+/// The payload itself is held by the `Ui` and may be accessed via `Ui::drag_and_drop_payload`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DragAndDrop {
+    /// The widget that started the drag-and-drop.
+    pub source: widget::Index,
+    /// The tag describing the type of the payload's data.
+    pub kind: &'static str,
+    /// The position of the mouse.
+    pub xy: Point,
+    /// Which modifier keys, if any, were being held down at the time.
+    pub modifiers: input::keyboard::ModifierKey,
+}
+
+/// Contains all relevant information for the end of a drag-and-drop.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DragEnd {
+    /// The widget upon which the payload was dropped, if any.
+    pub target: Option<widget::Index>,
+    /// Whether or not the target accepted the payload.
+    pub accepted: bool,
+}
+
+/// Contains all relevant information for an adjustment requested by a controller stick.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Adjust {
+    /// The amount by which the value along each axis should be adjusted, as a fraction of the
+    /// range of the value.
+    ///
+    /// Holding the stick fully to one side adjusts the value across its whole range each second.
+    pub amount: [Scalar; 2],
+}
+
+/// An event that bubbled up from a descendant that did not handle it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Bubble {
+    /// The descendant for which the event was originally produced.
+    pub source: widget::Index,
+    /// The event, with co-ordinates relative to the centre of the widget receiving the `Bubble`.
+    pub event: Box<Widget>,
+}
+
 /// Constructor for a new `RawEvent::Render`.
 pub fn render(dt_secs: f64, w_px: u32, h_px: u32, dpi: Scalar) -> RawEvent {
     RawEvent::Render(input::RenderArgs {
@@ -435,6 +710,16 @@ impl DoubleClick {
     }
 }
 
+impl MultiClick {
+    /// Returns a copy of the MultiClick relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> MultiClick {
+        MultiClick {
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
 impl Drag {
     /// Returns a copy of the Drag relative to the given `xy`
     pub fn relative_to(&self, xy: Point) -> Drag {
@@ -447,6 +732,88 @@ impl Drag {
     }
 }
 
+impl Hover {
+    /// Returns a copy of the Hover relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> Hover {
+        Hover {
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
+impl Touch {
+    /// Returns a copy of the Touch relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> Touch {
+        Touch {
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
+impl Tap {
+    /// Returns a copy of the Tap relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> Tap {
+        Tap {
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
+impl LongPress {
+    /// Returns a copy of the LongPress relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> LongPress {
+        LongPress {
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
+impl Pan {
+    /// Returns a copy of the Pan relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> Pan {
+        Pan {
+            origin: vec2_sub(self.origin, xy),
+            from: vec2_sub(self.from, xy),
+            to: vec2_sub(self.to, xy),
+            ..*self
+        }
+    }
+}
+
+impl Pinch {
+    /// Returns a copy of the Pinch relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> Pinch {
+        Pinch {
+            centre: vec2_sub(self.centre, xy),
+            ..*self
+        }
+    }
+}
+
+impl Rotate {
+    /// Returns a copy of the Rotate relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> Rotate {
+        Rotate {
+            centre: vec2_sub(self.centre, xy),
+            ..*self
+        }
+    }
+}
+
+impl DragAndDrop {
+    /// Returns a copy of the DragAndDrop relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> DragAndDrop {
+        DragAndDrop {
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
 
 impl From<Ui> for Event {
     fn from(ui: Ui) -> Self {
@@ -466,6 +833,12 @@ impl From<Text> for Widget {
     }
 }
 
+impl From<Composition> for Widget {
+    fn from(composition: Composition) -> Self {
+        Widget::Composition(composition)
+    }
+}
+
 impl From<Move> for Widget {
     fn from(move_: Move) -> Self {
         Widget::Move(move_)
@@ -496,14 +869,92 @@ impl From<DoubleClick> for Widget {
     }
 }
 
+impl From<MultiClick> for Widget {
+    fn from(multi_click: MultiClick) -> Self {
+        Widget::MultiClick(multi_click)
+    }
+}
+
 impl From<Scroll> for Widget {
     fn from(scroll: Scroll) -> Self {
         Widget::Scroll(scroll)
     }
 }
 
+impl From<Fling> for Widget {
+    fn from(fling: Fling) -> Self {
+        Widget::Fling(fling)
+    }
+}
+
 impl From<Drag> for Widget {
     fn from(drag: Drag) -> Self {
         Widget::Drag(drag)
     }
 }
+
+impl From<DragCancelled> for Widget {
+    fn from(drag_cancelled: DragCancelled) -> Self {
+        Widget::DragCancelled(drag_cancelled)
+    }
+}
+
+impl From<Touch> for Widget {
+    fn from(touch: Touch) -> Self {
+        Widget::Touch(touch)
+    }
+}
+
+impl From<Tap> for Widget {
+    fn from(tap: Tap) -> Self {
+        Widget::Tap(tap)
+    }
+}
+
+impl From<LongPress> for Widget {
+    fn from(long_press: LongPress) -> Self {
+        Widget::LongPress(long_press)
+    }
+}
+
+impl From<Pan> for Widget {
+    fn from(pan: Pan) -> Self {
+        Widget::Pan(pan)
+    }
+}
+
+impl From<Pinch> for Widget {
+    fn from(pinch: Pinch) -> Self {
+        Widget::Pinch(pinch)
+    }
+}
+
+impl From<Rotate> for Widget {
+    fn from(rotate: Rotate) -> Self {
+        Widget::Rotate(rotate)
+    }
+}
+
+impl From<Hover> for Widget {
+    fn from(hover: Hover) -> Self {
+        Widget::Hover(hover)
+    }
+}
+
+impl From<DragEnd> for Widget {
+    fn from(drag_end: DragEnd) -> Self {
+        Widget::DragEnd(drag_end)
+    }
+}
+
+impl From<Adjust> for Widget {
+    fn from(adjust: Adjust) -> Self {
+        Widget::Adjust(adjust)
+    }
+}
+
+impl From<Bubble> for Widget {
+    fn from(bubble: Bubble) -> Self {
+        Widget::Bubble(bubble)
+    }
+}
@@ -665,7 +665,7 @@ impl Graph {
         let widget::PreUpdateCache {
             type_id, idx, maybe_parent_idx, maybe_x_positioned_relatively_idx,
             maybe_y_positioned_relatively_idx, rect, depth, kid_area, maybe_floating,
-            crop_kids, maybe_x_scroll_state, maybe_y_scroll_state, maybe_graphics_for,
+            crop_kids, maybe_x_scroll_state, maybe_y_scroll_state, maybe_graphics_for, ..
         } = widget;
 
         // Construct a new `Container` to place in the `Graph`.
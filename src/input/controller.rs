@@ -0,0 +1,116 @@
+//! A table mapping the buttons and axes of game controllers to navigation between widgets.
+//!
+//! Controller buttons and axes are only identified by number, so the meaning of each depends upon
+//! the controller and backend. `Bindings::default` follows the SDL "GameController" layout used by
+//! most backends, though users may start from an empty table and bind each button themselves.
+
+use position::{Axis, Direction};
+use std;
+
+
+/// The actions that may be triggered by pressing a controller button.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Move keyboard focus to the nearest focusable widget in the given direction along the axis.
+    Navigate(Axis, Direction),
+    /// Activate the focused widget, as though `Return` were pressed.
+    Activate,
+    /// Cancel the current interaction, as though `Escape` were pressed.
+    Cancel,
+}
+
+/// A table mapping controller buttons to `Action`s, along with the axes used for navigation and
+/// for adjusting the value of the focused widget.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bindings {
+    buttons: std::collections::HashMap<u8, Action>,
+    /// The horizontal and vertical axes of the stick used to navigate between widgets.
+    pub navigation_axes: Option<[u8; 2]>,
+    /// The horizontal and vertical axes of the stick used to adjust the value of the focused
+    /// widget, i.e. a `Slider`, `NumberDialer` or `XYPad`.
+    pub adjustment_axes: Option<[u8; 2]>,
+    /// Axis positions closer than this to the centre are ignored.
+    ///
+    /// Positions range from `-1.0` to `1.0`, with positive vertical positions pointing downwards.
+    pub dead_zone: f64,
+}
+
+// The SDL "GameController" layout.
+const BUTTON_A: u8 = 0;
+const BUTTON_B: u8 = 1;
+const BUTTON_DPAD_UP: u8 = 11;
+const BUTTON_DPAD_DOWN: u8 = 12;
+const BUTTON_DPAD_LEFT: u8 = 13;
+const BUTTON_DPAD_RIGHT: u8 = 14;
+const AXIS_LEFT_X: u8 = 0;
+const AXIS_LEFT_Y: u8 = 1;
+const AXIS_RIGHT_X: u8 = 2;
+const AXIS_RIGHT_Y: u8 = 3;
+
+
+impl Bindings {
+
+    /// An empty table with no bindings.
+    pub fn new() -> Self {
+        Bindings {
+            buttons: std::collections::HashMap::new(),
+            navigation_axes: None,
+            adjustment_axes: None,
+            dead_zone: 0.5,
+        }
+    }
+
+    /// Bind the given button to the given action, replacing any existing binding.
+    pub fn bind(mut self, button: u8, action: Action) -> Self {
+        self.buttons.insert(button, action);
+        self
+    }
+
+    /// Specify the horizontal and vertical axes used to navigate between widgets.
+    pub fn navigation_axes(mut self, x: u8, y: u8) -> Self {
+        self.navigation_axes = Some([x, y]);
+        self
+    }
+
+    /// Specify the horizontal and vertical axes used to adjust the value of the focused widget.
+    pub fn adjustment_axes(mut self, x: u8, y: u8) -> Self {
+        self.adjustment_axes = Some([x, y]);
+        self
+    }
+
+    /// Specify the distance from the centre within which axis positions are ignored.
+    pub fn dead_zone(mut self, dead_zone: f64) -> Self {
+        self.dead_zone = dead_zone;
+        self
+    }
+
+    /// The action bound to the given button, if any.
+    pub fn action(&self, button: u8) -> Option<Action> {
+        self.buttons.get(&button).cloned()
+    }
+
+    /// The position of the given axis with the dead zone removed, rescaled to `-1.0...1.0`.
+    pub fn filter_dead_zone(&self, position: f64) -> f64 {
+        let magnitude = position.abs();
+        if magnitude <= self.dead_zone || self.dead_zone >= 1.0 {
+            return 0.0;
+        }
+        let rescaled = (magnitude.min(1.0) - self.dead_zone) / (1.0 - self.dead_zone);
+        rescaled * position.signum()
+    }
+
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Bindings::new()
+            .bind(BUTTON_A, Action::Activate)
+            .bind(BUTTON_B, Action::Cancel)
+            .bind(BUTTON_DPAD_UP, Action::Navigate(Axis::Y, Direction::Forwards))
+            .bind(BUTTON_DPAD_DOWN, Action::Navigate(Axis::Y, Direction::Backwards))
+            .bind(BUTTON_DPAD_LEFT, Action::Navigate(Axis::X, Direction::Backwards))
+            .bind(BUTTON_DPAD_RIGHT, Action::Navigate(Axis::X, Direction::Forwards))
+            .navigation_axes(AXIS_LEFT_X, AXIS_LEFT_Y)
+            .adjustment_axes(AXIS_RIGHT_X, AXIS_RIGHT_Y)
+    }
+}
@@ -0,0 +1,102 @@
+//! Types used to carry some data from one widget to another by dragging it with the mouse.
+//!
+//! A widget starts a drag-and-drop by passing a `Payload` to `UiCell::start_drag_and_drop` while
+//! the left mouse button is down. While the mouse moves, the widgets beneath it receive
+//! `DragEnter`, `DragOver` and `DragLeave` events. Once the button is released, the widget beneath
+//! the mouse receives a `Drop` event, which it may accept by taking the `Payload` via
+//! `UiCell::accept_drop`. The widget that started the drag-and-drop then receives a `DragEnd`
+//! event describing whether or not the `Payload` was accepted.
+
+use std;
+use std::any::Any;
+use widget;
+
+
+/// Some data carried by the mouse from the widget that started a drag-and-drop to the widget upon
+/// which it is dropped.
+///
+/// The `kind` is a tag describing the type of the data, allowing widgets to decide whether or not
+/// they are interested in the payload without knowing the type of the data itself.
+pub struct Payload {
+    kind: &'static str,
+    data: Box<Any>,
+    maybe_preview: Option<String>,
+}
+
+/// The state of a drag-and-drop that is currently in progress.
+#[derive(Debug)]
+pub struct DragAndDrop {
+    /// The widget that started the drag-and-drop.
+    pub source: widget::Index,
+    /// The data carried by the drag-and-drop.
+    pub payload: Payload,
+    /// The widget over which the payload is currently being dragged.
+    pub maybe_target: Option<widget::Index>,
+}
+
+
+impl Payload {
+
+    /// Construct a new `Payload` carrying the given `data` of the given `kind`.
+    pub fn new<T: Any>(kind: &'static str, data: T) -> Self {
+        Payload {
+            kind: kind,
+            data: Box::new(data),
+            maybe_preview: None,
+        }
+    }
+
+    /// Text displayed near the mouse while the payload is being dragged.
+    pub fn preview<S: Into<String>>(mut self, text: S) -> Self {
+        self.maybe_preview = Some(text.into());
+        self
+    }
+
+    /// The tag describing the type of the data.
+    pub fn kind(&self) -> &'static str {
+        self.kind
+    }
+
+    /// The text displayed near the mouse while the payload is being dragged, if any.
+    pub fn preview_text(&self) -> Option<&str> {
+        self.maybe_preview.as_ref().map(|s| &s[..])
+    }
+
+    /// Whether or not the data is of type `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.data.is::<T>()
+    }
+
+    /// A reference to the data if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.data.downcast_ref::<T>()
+    }
+
+    /// Take ownership of the data if it is of type `T`, otherwise return the `Payload`.
+    pub fn downcast<T: Any>(self) -> Result<Box<T>, Self> {
+        let Payload { kind, data, maybe_preview } = self;
+        data.downcast::<T>().map_err(|data| Payload {
+            kind: kind,
+            data: data,
+            maybe_preview: maybe_preview,
+        })
+    }
+
+}
+
+impl std::fmt::Debug for Payload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Payload {{ kind: {:?}, preview: {:?} }}", self.kind, self.maybe_preview)
+    }
+}
+
+
+#[test]
+fn payload_should_downcast_to_the_type_of_its_data() {
+    let payload = Payload::new("number", 42u32);
+    assert!(payload.is::<u32>());
+    assert_eq!(payload.downcast_ref::<u32>(), Some(&42));
+    let payload = payload.downcast::<String>().unwrap_err();
+    assert_eq!(payload.kind(), "number");
+    assert_eq!(*payload.downcast::<u32>().unwrap(), 42);
+}
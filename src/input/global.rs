@@ -20,6 +20,18 @@ pub struct Global {
     /// Tracks the last click that occurred and the time at which it occurred in order to create
     /// double-click events.
     pub last_click: Option<(std::time::Instant, event::Click)>,
+    /// The number of consecutive clicks ending with the `last_click`, used to create multi-click
+    /// events.
+    pub click_count: u32,
+    /// The state of each finger that is currently touching the screen.
+    pub touch: input::state::touch::Map,
+    /// The number of seconds for which the mouse has rested over the `widget_under_mouse`, as
+    /// accumulated from the `dt` of each `Render` event. Used to create hover events.
+    pub mouse_rest_secs: f64,
+    /// Whether or not a `Hover` has been produced since the mouse last moved.
+    pub has_hovered: bool,
+    /// The latest position of each axis of each controller, keyed by controller and axis.
+    pub controller_axes: std::collections::HashMap<(i32, u8), f64>,
 }
 
 /// Iterator over all global `event::Event`s that have occurred since the last time
@@ -36,6 +48,13 @@ pub struct UiEvents<'a> {
     events: Events<'a>,
 }
 
+/// An iterator yielding the `Id` of each keyboard shortcut that has been triggered since the last
+/// time `Ui::set_widgets` was called.
+#[derive(Clone)]
+pub struct Shortcuts<'a> {
+    events: Events<'a>,
+}
+
 impl Global {
 
     /// Returns a fresh new `Global`
@@ -45,6 +64,11 @@ impl Global {
             start: input::State::new(),
             current: input::State::new(),
             last_click: None,
+            click_count: 0,
+            touch: input::state::touch::Map::new(),
+            mouse_rest_secs: 0.0,
+            has_hovered: false,
+            controller_axes: std::collections::HashMap::new(),
         }
     }
 
@@ -74,6 +98,12 @@ impl<'a> Events<'a> {
     pub fn ui(self) -> UiEvents<'a> {
         UiEvents { events: self }
     }
+
+    /// Converts the `Events` into a `Shortcuts`, yielding the `Id` of each keyboard shortcut that
+    /// has been triggered since the last time `Ui::set_widgets` was called.
+    pub fn shortcuts(self) -> Shortcuts<'a> {
+        Shortcuts { events: self }
+    }
 }
 
 impl<'a> Iterator for Events<'a> {
@@ -94,3 +124,15 @@ impl<'a> Iterator for UiEvents<'a> {
         None
     }
 }
+
+impl<'a> Iterator for Shortcuts<'a> {
+    type Item = input::shortcut::Id;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Event::Ui(event::Ui::Shortcut(id)) = *event {
+                return Some(id);
+            }
+        }
+        None
+    }
+}
@@ -18,6 +18,9 @@
 //! will never filter out any events, and will always provide them with coordinates relative to the
 //! window.
 
+pub mod controller;
+pub mod drag_and_drop;
+pub mod shortcut;
 pub mod state;
 pub mod widget;
 pub mod global;
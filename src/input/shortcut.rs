@@ -0,0 +1,158 @@
+//! Application-wide keyboard shortcuts, such as `Ctrl+S` or the `Ctrl+K Ctrl+S` chord.
+//!
+//! Shortcuts are registered with the `Ui` via `Ui::register_shortcut`. Each time a registered
+//! `Shortcut` is completed, the `Ui` produces an `event::Ui::Shortcut` carrying its `Id`, unless
+//! the widget capturing the keyboard consumes the key press (see `UiCell::consume_key`).
+
+use input::Key;
+use input::keyboard::{self, ModifierKey};
+
+
+/// A unique identifier for a `Shortcut`, chosen by the user upon registering it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Id(pub usize);
+
+/// A single key pressed while holding some set of modifier keys, i.e. `Ctrl+Shift+P`.
+///
+/// The modifiers must match exactly, i.e. `Ctrl+P` will not be triggered by `Ctrl+Shift+P`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Combo {
+    /// The modifier keys that must be held.
+    pub modifiers: ModifierKey,
+    /// The key that must be pressed.
+    pub key: Key,
+}
+
+/// A sequence of one or more `Combo`s that must be pressed one after the other.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    combos: Vec<Combo>,
+}
+
+/// The set of `Shortcut`s registered with the `Ui`, along with the progress made through them.
+#[derive(Clone, Debug)]
+pub struct Registry {
+    shortcuts: Vec<(Id, Shortcut)>,
+    pressed: Vec<Combo>,
+}
+
+
+impl Combo {
+
+    /// Construct a new `Combo`.
+    pub fn new(modifiers: ModifierKey, key: Key) -> Self {
+        Combo {
+            modifiers: modifiers,
+            key: key,
+        }
+    }
+
+    /// Whether or not the `Combo` is usually used to enter text, i.e. a character key pressed
+    /// without `Ctrl`, `Alt` or `Gui`.
+    pub fn enters_text(&self) -> bool {
+        let code = self.key as u64;
+        let is_character_key = code >= Key::Space as u64 && code < Key::Delete as u64;
+        let is_command = self.modifiers.intersects(keyboard::CTRL | keyboard::ALT | keyboard::GUI);
+        is_character_key && !is_command
+    }
+
+}
+
+impl Shortcut {
+
+    /// A `Shortcut` triggered by pressing the given `key` while holding the given `modifiers`.
+    pub fn new(modifiers: ModifierKey, key: Key) -> Self {
+        Shortcut { combos: vec![Combo::new(modifiers, key)] }
+    }
+
+    /// Extend the `Shortcut` into a chord that must be followed by the given combination.
+    pub fn then(mut self, modifiers: ModifierKey, key: Key) -> Self {
+        self.combos.push(Combo::new(modifiers, key));
+        self
+    }
+
+    /// The sequence of `Combo`s that trigger the `Shortcut`.
+    pub fn combos(&self) -> &[Combo] {
+        &self.combos
+    }
+
+}
+
+impl Registry {
+
+    /// An empty `Registry`.
+    pub fn new() -> Self {
+        Registry {
+            shortcuts: Vec::new(),
+            pressed: Vec::new(),
+        }
+    }
+
+    /// Register the given `Shortcut`, replacing any that was previously registered with the `id`.
+    pub fn register(&mut self, id: Id, shortcut: Shortcut) {
+        self.unregister(id);
+        self.shortcuts.push((id, shortcut));
+    }
+
+    /// Remove the `Shortcut` registered with the given `id`, if any.
+    pub fn unregister(&mut self, id: Id) {
+        self.shortcuts.retain(|&(registered, _)| registered != id);
+        self.pressed.clear();
+    }
+
+    /// Produce an iterator yielding each registered `Shortcut` along with its `Id`.
+    pub fn iter(&self) -> ::std::slice::Iter<(Id, Shortcut)> {
+        self.shortcuts.iter()
+    }
+
+    /// Forget any partially pressed chord.
+    pub fn reset(&mut self) {
+        self.pressed.clear();
+    }
+
+    /// Advance through the registered `Shortcut`s with the given key press.
+    ///
+    /// Returns the `Id` of the `Shortcut` completed by the press, if any. Presses that neither
+    /// complete nor continue a chord restart it, so that they may begin a new one.
+    pub fn press(&mut self, combo: Combo) -> Option<Id> {
+        self.pressed.push(combo);
+        if !self.continues_chord() {
+            self.pressed.clear();
+            self.pressed.push(combo);
+            if !self.continues_chord() {
+                self.pressed.clear();
+                return None;
+            }
+        }
+
+        let maybe_id = self.shortcuts.iter()
+            .find(|&&(_, ref shortcut)| shortcut.combos == self.pressed)
+            .map(|&(id, _)| id);
+        if maybe_id.is_some() {
+            self.pressed.clear();
+        }
+        maybe_id
+    }
+
+    // Whether or not the `pressed` combos are the start of at least one `Shortcut`.
+    fn continues_chord(&self) -> bool {
+        self.shortcuts.iter().any(|&(_, ref shortcut)| shortcut.combos.starts_with(&self.pressed))
+    }
+
+}
+
+
+#[test]
+fn chords_should_only_trigger_once_each_combo_is_pressed_in_order() {
+    use input::keyboard::{CTRL, NO_MODIFIER};
+    let mut registry = Registry::new();
+    registry.register(Id(0), Shortcut::new(CTRL, Key::S));
+    registry.register(Id(1), Shortcut::new(CTRL, Key::K).then(CTRL, Key::S));
+    assert_eq!(registry.press(Combo::new(CTRL, Key::S)), Some(Id(0)));
+    assert_eq!(registry.press(Combo::new(CTRL, Key::K)), None);
+    assert_eq!(registry.press(Combo::new(CTRL, Key::S)), Some(Id(1)));
+    // An unrelated press abandons the chord.
+    assert_eq!(registry.press(Combo::new(CTRL, Key::K)), None);
+    assert_eq!(registry.press(Combo::new(NO_MODIFIER, Key::S)), None);
+    assert_eq!(registry.press(Combo::new(CTRL, Key::S)), Some(Id(0)));
+}
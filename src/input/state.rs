@@ -245,6 +245,47 @@ pub mod mouse {
 
 }
 
+/// Touch specific state.
+pub mod touch {
+    use position::Point;
+    use std;
+    use widget;
+
+    /// A unique identifier for a finger touching the screen, as provided by the backend.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct Id(pub i64);
+
+    /// The state of a single finger that is touching the screen.
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub struct Touch {
+        /// The time at which the finger first touched the screen.
+        pub start_time: std::time::Instant,
+        /// The position at which the finger first touched the screen.
+        pub start_xy: Point,
+        /// The widget that was under the finger when it first touched the screen.
+        ///
+        /// This widget receives all events produced by the finger until it is lifted.
+        pub widget: Option<widget::Index>,
+        /// The current position of the finger.
+        pub xy: Point,
+        /// Whether or not the finger has moved further than the `theme.touch_drag_threshold` from
+        /// the `start_xy`, in which case it no longer produces `Tap`s or `LongPress`es.
+        pub has_moved: bool,
+        /// Whether or not a `LongPress` has been produced for the finger.
+        pub is_long_press: bool,
+        /// The time at which the finger last moved.
+        pub move_time: std::time::Instant,
+        /// The recent velocity of the finger in points per second.
+        pub velocity: Point,
+        /// The scrollable widget whose content the finger is panning, if any.
+        pub panned_widget: Option<widget::Index>,
+    }
+
+    /// The state of all fingers that are currently touching the screen.
+    pub type Map = std::collections::HashMap<Id, Touch>;
+
+}
+
 
 
 #[test]
@@ -5,7 +5,9 @@
 
 use {Point, Rect};
 use event;
+use graph::{Graph, NodeIndex};
 use input;
+use std::collections::HashSet;
 use utils;
 use widget;
 
@@ -22,6 +24,19 @@ pub struct Widget<'a> {
     global: &'a input::Global,
     rect: Rect,
     idx: widget::Index,
+    maybe_bubbling: Option<Bubbling<'a>>,
+}
+
+/// The state used to determine which of the events left unhandled by a widget's descendants bubble
+/// up to it along their `Edge::Depth` parents.
+#[derive(Copy, Clone)]
+pub struct Bubbling<'a> {
+    /// The graph along whose `Edge::Depth`s events bubble.
+    pub graph: &'a Graph,
+    /// The widgets that have been updated so far during the current call to `Ui::set_widgets`.
+    pub updated_widgets: &'a HashSet<NodeIndex>,
+    /// The events marked as handled so far, each paired with the widget that handled it.
+    pub handled_events: &'a [(widget::Index, event::Widget)],
 }
 
 /// A view of the `input::state::Mouse` that is specific to a single widget.
@@ -47,6 +62,7 @@ pub struct Events<'a> {
     capturing_mouse: Option<widget::Index>,
     rect: Rect,
     idx: widget::Index,
+    maybe_bubbling: Option<Bubbling<'a>>,
 }
 
 /// An `Iterator` yielding all button presses occuring within the given sequence of
@@ -151,6 +167,67 @@ pub struct Scrolls<'a> {
     events: Events<'a>,
 }
 
+/// An iterator that yields all `Fling` events yielded by the given `Events` iterator.
+#[derive(Clone)]
+pub struct Flings<'a> {
+    events: Events<'a>,
+}
+
+/// An iterator that yields all `event::Touch` events yielded by the given `Events` iterator.
+#[derive(Clone)]
+pub struct Touches<'a> {
+    events: Events<'a>,
+}
+
+/// An iterator that yields all `event::Tap` events yielded by the given `Events` iterator.
+#[derive(Clone)]
+pub struct Taps<'a> {
+    events: Events<'a>,
+}
+
+/// An iterator that yields all `event::LongPress` events yielded by the given `Events` iterator.
+#[derive(Clone)]
+pub struct LongPresses<'a> {
+    events: Events<'a>,
+}
+
+/// An iterator that yields all `event::Pan` events yielded by the given `Events` iterator.
+#[derive(Clone)]
+pub struct Pans<'a> {
+    events: Events<'a>,
+}
+
+/// An iterator that yields all `event::Pinch` events yielded by the given `Events` iterator.
+#[derive(Clone)]
+pub struct Pinches<'a> {
+    events: Events<'a>,
+}
+
+/// An iterator that yields all `event::Rotate` events yielded by the given `Events` iterator.
+#[derive(Clone)]
+pub struct Rotations<'a> {
+    events: Events<'a>,
+}
+
+/// An iterator that yields the `event::DragAndDrop` of each `Drop` event yielded by the given
+/// `Events` iterator.
+#[derive(Clone)]
+pub struct Drops<'a> {
+    events: Events<'a>,
+}
+
+/// An iterator that yields all `event::Adjust` events yielded by the given `Events` iterator.
+#[derive(Clone)]
+pub struct Adjustments<'a> {
+    events: Events<'a>,
+}
+
+/// An iterator that yields all `event::Bubble` events yielded by the given `Events` iterator.
+#[derive(Clone)]
+pub struct Bubbles<'a> {
+    events: Events<'a>,
+}
+
 
 impl<'a> Widget<'a> {
 
@@ -164,9 +241,16 @@ impl<'a> Widget<'a> {
             global: global,
             rect: rect,
             idx: idx,
+            maybe_bubbling: None,
         }
     }
 
+    /// Also yield the events that bubble up to the widget from descendants that did not handle
+    /// them, as `event::Widget::Bubble`s.
+    pub fn with_bubbling(self, bubbling: Bubbling<'a>) -> Self {
+        Widget { maybe_bubbling: Some(bubbling), ..self }
+    }
+
     /// If the widget is currently capturing the mouse, this returns the state of the mouse.
     ///
     /// Returns `None` if the widget is not capturing the mouse.
@@ -196,6 +280,7 @@ impl<'a> Widget<'a> {
             capturing_mouse: self.global.start.widget_capturing_mouse,
             rect: self.rect,
             idx: self.idx,
+            maybe_bubbling: self.maybe_bubbling,
         }
     }
 
@@ -238,6 +323,65 @@ impl<'a> Widget<'a> {
         Scrolls { events: self.events() }
     }
 
+    /// Produce an iterator that yields only the `Fling` events yielded by the `Events` iterator.
+    pub fn flings(&self) -> Flings<'a> {
+        Flings { events: self.events() }
+    }
+
+    /// Produce an iterator that yields only the `Touch` events yielded by the `Events` iterator.
+    ///
+    /// Only events produced by fingers that first touched the screen over the widget are yielded.
+    pub fn touches(&self) -> Touches<'a> {
+        Touches { events: self.events() }
+    }
+
+    /// Produce an iterator that yields only the `Tap` events yielded by the `Events` iterator.
+    pub fn taps(&self) -> Taps<'a> {
+        Taps { events: self.events() }
+    }
+
+    /// Produce an iterator that yields only the `LongPress` events yielded by the `Events`
+    /// iterator.
+    pub fn long_presses(&self) -> LongPresses<'a> {
+        LongPresses { events: self.events() }
+    }
+
+    /// Produce an iterator that yields only the `Pan` events yielded by the `Events` iterator.
+    pub fn pans(&self) -> Pans<'a> {
+        Pans { events: self.events() }
+    }
+
+    /// Produce an iterator that yields only the `Pinch` events yielded by the `Events` iterator.
+    pub fn pinches(&self) -> Pinches<'a> {
+        Pinches { events: self.events() }
+    }
+
+    /// Produce an iterator that yields only the `Rotate` events yielded by the `Events` iterator.
+    pub fn rotations(&self) -> Rotations<'a> {
+        Rotations { events: self.events() }
+    }
+
+    /// Produce an iterator that yields only the `Drop` events yielded by the `Events` iterator.
+    ///
+    /// The payload of each drop may be accepted using `UiCell::accept_drop`.
+    pub fn drops(&self) -> Drops<'a> {
+        Drops { events: self.events() }
+    }
+
+    /// Produce an iterator that yields only the `Adjust` events yielded by the `Events` iterator.
+    pub fn adjustments(&self) -> Adjustments<'a> {
+        Adjustments { events: self.events() }
+    }
+
+    /// Produce an iterator that yields only the events that bubbled up from descendants that did
+    /// not handle them.
+    ///
+    /// Events that are handled here may be marked using `UiCell::set_event_handled` to stop them
+    /// from bubbling further. Nothing is yielded unless `UiBuilder::bubble_events` is enabled.
+    pub fn bubbles(&self) -> Bubbles<'a> {
+        Bubbles { events: self.events() }
+    }
+
 }
 
 impl<'a> Mouse<'a> {
@@ -448,12 +592,38 @@ impl<'a> Iterator for Events<'a> {
                     }
                 },
 
+                // Hovering.
+                event::Ui::MouseEnter(idx) if idx == self.idx =>
+                    return Some(event::Widget::MouseEnter),
+                event::Ui::MouseLeave(idx) if idx == self.idx =>
+                    return Some(event::Widget::MouseLeave),
+                event::Ui::Hover(idx, ref hover) if idx == Some(self.idx) =>
+                    return Some(hover.relative_to(self.rect.xy()).into()),
+
+                // Drag-and-drop.
+                event::Ui::DragEnter(idx, ref dnd) if idx == self.idx =>
+                    return Some(event::Widget::DragEnter(dnd.relative_to(self.rect.xy()))),
+                event::Ui::DragOver(idx, ref dnd) if idx == self.idx =>
+                    return Some(event::Widget::DragOver(dnd.relative_to(self.rect.xy()))),
+                event::Ui::DragLeave(idx, ref dnd) if idx == self.idx =>
+                    return Some(event::Widget::DragLeave(dnd.relative_to(self.rect.xy()))),
+                event::Ui::Drop(idx, ref dnd) if idx == self.idx =>
+                    return Some(event::Widget::Drop(dnd.relative_to(self.rect.xy()))),
+                event::Ui::DragEnd(idx, drag_end) if idx == self.idx =>
+                    return Some(drag_end.into()),
+
+                event::Ui::Adjust(idx, adjust) if idx == Some(self.idx) =>
+                    return Some(adjust.into()),
+
                 event::Ui::WindowResized(dim) =>
                     return Some(event::Widget::WindowResized(dim)),
 
                 event::Ui::Text(idx, ref text) if idx == Some(self.idx) =>
                     return Some(text.clone().into()),
 
+                event::Ui::Composition(idx, ref composition) if idx == Some(self.idx) =>
+                    return Some(composition.clone().into()),
+
                 event::Ui::Move(idx, ref move_) if idx == Some(self.idx) =>
                     return Some(move_.clone().into()),
 
@@ -469,15 +639,49 @@ impl<'a> Iterator for Events<'a> {
                 event::Ui::DoubleClick(idx, ref double_click) if idx == Some(self.idx) =>
                     return Some(double_click.clone().relative_to(self.rect.xy()).into()),
 
+                event::Ui::MultiClick(idx, ref multi_click) if idx == Some(self.idx) =>
+                    return Some(multi_click.clone().relative_to(self.rect.xy()).into()),
+
                 event::Ui::Drag(idx, ref drag) if idx == Some(self.idx) =>
                     return Some(drag.clone().relative_to(self.rect.xy()).into()),
 
+                event::Ui::DragCancelled(idx, drag_cancelled) if idx == Some(self.idx) =>
+                    return Some(drag_cancelled.into()),
+
                 event::Ui::Scroll(idx, ref scroll) if idx == Some(self.idx) =>
                     return Some(scroll.clone().into()),
 
+                event::Ui::Fling(idx, fling) if idx == Some(self.idx) =>
+                    return Some(fling.into()),
+
+                event::Ui::Touch(idx, ref touch) if idx == Some(self.idx) =>
+                    return Some(touch.relative_to(self.rect.xy()).into()),
+
+                event::Ui::Tap(idx, ref tap) if idx == Some(self.idx) =>
+                    return Some(tap.relative_to(self.rect.xy()).into()),
+
+                event::Ui::LongPress(idx, ref long_press) if idx == Some(self.idx) =>
+                    return Some(long_press.relative_to(self.rect.xy()).into()),
+
+                event::Ui::Pan(idx, ref pan) if idx == Some(self.idx) =>
+                    return Some(pan.relative_to(self.rect.xy()).into()),
+
+                event::Ui::Pinch(idx, ref pinch) if idx == Some(self.idx) =>
+                    return Some(pinch.relative_to(self.rect.xy()).into()),
+
+                event::Ui::Rotate(idx, ref rotate) if idx == Some(self.idx) =>
+                    return Some(rotate.relative_to(self.rect.xy()).into()),
+
                 _ => (),
                 
             }
+
+            // Otherwise, the event may have bubbled up from one of the widget's descendants.
+            if let Some(bubbling) = self.maybe_bubbling {
+                if let Some(bubble) = bubbling.bubble(ui_event, self.idx, self.rect) {
+                    return Some(bubble.into());
+                }
+            }
         }
 
         None
@@ -652,3 +856,214 @@ impl<'a> Iterator for Scrolls<'a> {
         None
     }
 }
+
+impl<'a> Iterator for Flings<'a> {
+    type Item = event::Fling;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::Fling(fling) = event {
+                return Some(fling);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for Touches<'a> {
+    type Item = event::Touch;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::Touch(touch) = event {
+                return Some(touch);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for Taps<'a> {
+    type Item = event::Tap;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::Tap(tap) = event {
+                return Some(tap);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for LongPresses<'a> {
+    type Item = event::LongPress;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::LongPress(long_press) = event {
+                return Some(long_press);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for Pans<'a> {
+    type Item = event::Pan;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::Pan(pan) = event {
+                return Some(pan);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for Pinches<'a> {
+    type Item = event::Pinch;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::Pinch(pinch) = event {
+                return Some(pinch);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for Rotations<'a> {
+    type Item = event::Rotate;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::Rotate(rotate) = event {
+                return Some(rotate);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for Drops<'a> {
+    type Item = event::DragAndDrop;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::Drop(dnd) = event {
+                return Some(dnd);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for Adjustments<'a> {
+    type Item = event::Adjust;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::Adjust(adjust) = event {
+                return Some(adjust);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for Bubbles<'a> {
+    type Item = event::Bubble;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::Bubble(bubble) = event {
+                return Some(bubble);
+            }
+        }
+        None
+    }
+}
+
+
+impl<'a> Bubbling<'a> {
+
+    /// The `event::Bubble` received by the widget at `idx`, occupying the given `rect`, for the
+    /// given event.
+    ///
+    /// Returns `None` unless the event was received by one of the widget's descendants that was
+    /// updated during the current call to `Ui::set_widgets`, and was left unhandled by that
+    /// descendant and every ancestor between it and the widget.
+    pub fn bubble(&self, ui_event: &event::Ui, idx: widget::Index, rect: Rect)
+        -> Option<event::Bubble>
+    {
+        let source = match bubbling_target(ui_event) {
+            Some(source) if source != idx => source,
+            _ => return None,
+        };
+        let is_updated = self.graph.node_index(source)
+            .map(|node_idx| self.updated_widgets.contains(&node_idx))
+            .unwrap_or(false);
+        if !is_updated {
+            return None;
+        }
+
+        // Walk up the depth parents of the source, checking whether each has handled the event.
+        let mut handler = source;
+        loop {
+            let handler_rect = match self.graph.widget(handler) {
+                Some(widget) => widget.rect,
+                None => return None,
+            };
+            let event = match bubbling_event(ui_event, handler_rect) {
+                Some(event) if handler == source => event,
+                Some(event) => event::Bubble { source: source, event: Box::new(event) }.into(),
+                None => return None,
+            };
+            let is_handled = self.handled_events.iter()
+                .any(|&(i, ref handled)| i == handler && *handled == event);
+            if is_handled {
+                return None;
+            }
+            match self.graph.depth_parent(handler) {
+                Some(parent) if parent == idx => break,
+                Some(parent) => handler = parent,
+                None => return None,
+            }
+        }
+
+        bubbling_event(ui_event, rect)
+            .map(|event| event::Bubble { source: source, event: Box::new(event) })
+    }
+
+}
+
+// The widget that received the given event, if it is of a kind that bubbles up to the widget's
+// ancestors when left unhandled.
+fn bubbling_target(event: &event::Ui) -> Option<widget::Index> {
+    match *event {
+        event::Ui::Text(idx, _) |
+        event::Ui::Press(idx, _) |
+        event::Ui::Release(idx, _) |
+        event::Ui::Click(idx, _) |
+        event::Ui::DoubleClick(idx, _) |
+        event::Ui::MultiClick(idx, _) |
+        event::Ui::Drag(idx, _) |
+        event::Ui::Scroll(idx, _) |
+        event::Ui::Tap(idx, _) |
+        event::Ui::LongPress(idx, _) => idx,
+        _ => None,
+    }
+}
+
+// Produce the event received by a widget occupying the given `rect` for the given `event::Ui`, if
+// it is of a kind that bubbles up to ancestors when left unhandled.
+fn bubbling_event(ui_event: &event::Ui, rect: Rect) -> Option<event::Widget> {
+    let xy = rect.xy();
+    let event = match *ui_event {
+        event::Ui::Text(_, ref text) => text.clone().into(),
+        event::Ui::Press(_, ref press) => press.clone().relative_to(xy).into(),
+        event::Ui::Release(_, ref release) => release.clone().relative_to(xy).into(),
+        event::Ui::Click(_, ref click) => click.clone().relative_to(xy).into(),
+        event::Ui::DoubleClick(_, ref double_click) => double_click.clone().relative_to(xy).into(),
+        event::Ui::MultiClick(_, ref multi_click) => multi_click.clone().relative_to(xy).into(),
+        event::Ui::Drag(_, ref drag) => drag.clone().relative_to(xy).into(),
+        event::Ui::Scroll(_, ref scroll) => scroll.clone().into(),
+        event::Ui::Tap(_, ref tap) => tap.relative_to(xy).into(),
+        event::Ui::LongPress(_, ref long_press) => long_press.relative_to(xy).into(),
+        _ => return None,
+    };
+    Some(event)
+}
@@ -13,6 +13,7 @@ extern crate num;
 extern crate input as piston_input;
 extern crate rusttype;
 #[macro_use] extern crate json;
+extern crate unicode_segmentation;
 
 
 pub use color::{Color, Colorable};
@@ -28,7 +29,9 @@ pub use widget::{scroll, Widget};
 
 pub mod backend;
 mod border;
+pub mod clipboard;
 pub mod color;
+pub mod cursor;
 pub mod event;
 pub mod graph;
 pub mod guide;
@@ -36,6 +39,7 @@ pub mod image;
 pub mod input;
 mod label;
 mod position;
+pub mod record;
 pub mod render;
 pub mod text;
 pub mod theme;
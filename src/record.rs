@@ -0,0 +1,533 @@
+//! Recording the raw events given to a `Ui` and replaying them deterministically.
+//!
+//! A `Recording` is a list of the events given to a `Ui`, each timestamped with the time elapsed
+//! since the recording began. Recordings are started and stopped via `Ui::start_recording` and
+//! `Ui::stop_recording`, and may be saved to and loaded from files, with one JSON object per line.
+//!
+//! A `Player` feeds a `Recording` back to a `Ui`. Before handing each event to the `Ui`, the
+//! `Player` sets the `Ui`'s clock (see `Ui::set_clock`) to the time at which the event was
+//! recorded, so that time-based interpretation such as `DoubleClick`s and `LongPress`es reproduces
+//! exactly regardless of how quickly the events are replayed. This allows for reproducing bug
+//! reports and for driving regression tests against the resulting widget events.
+//!
+//! Both the events given to `Ui::handle_event` and the input method compositions given to
+//! `Ui::handle_composition` are recorded.
+
+use event::{self, Input, Motion, RawEvent};
+use input;
+use json::{self, JsonValue};
+use std;
+use std::time::{Duration, Instant};
+use ui::Ui;
+
+
+/// A list of `Event`s, each along with the time elapsed since the recording began.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recording {
+    events: Vec<(Duration, Event)>,
+}
+
+/// An event given to a `Ui`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// An event given via `Ui::handle_event`.
+    Raw(event::Raw),
+    /// An input method composition given via `Ui::handle_composition`.
+    Composition(event::Composition),
+}
+
+/// Appends each event given to a `Ui` to a `Recording`.
+///
+/// See `Ui::start_recording`.
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    start: Instant,
+    recording: Recording,
+}
+
+/// Feeds the events of a `Recording` back to a `Ui` using a fake clock.
+#[derive(Clone, Debug)]
+pub struct Player {
+    recording: Recording,
+    next: usize,
+    start: Instant,
+}
+
+/// Returned when saving or loading a `Recording` fails.
+#[derive(Debug)]
+pub enum Error {
+    /// Some error occurred while reading or writing a file.
+    IO(std::io::Error),
+    /// Some line of the recording was not valid JSON.
+    Json(json::Error),
+    /// The JSON on the given (zero-based) line did not describe an event.
+    InvalidEvent(usize),
+}
+
+
+impl Recording {
+
+    /// An empty `Recording`.
+    pub fn new() -> Self {
+        Recording { events: Vec::new() }
+    }
+
+    /// Append the given event, received the given duration after the recording began.
+    pub fn push<E: Into<Event>>(&mut self, time: Duration, event: E) {
+        self.events.push((time, event.into()));
+    }
+
+    /// The recorded events along with the time at which each was received.
+    pub fn events(&self) -> &[(Duration, Event)] {
+        &self.events
+    }
+
+    /// The number of recorded events.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Whether or not no events were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Encode the `Recording` as one JSON object per line.
+    ///
+    /// Events that cannot affect the `Ui` (i.e. `Update`, `AfterRender` and `Idle`) are skipped.
+    pub fn to_json_lines(&self) -> String {
+        let mut string = String::new();
+        for &(time, ref event) in &self.events {
+            if let Some(mut json) = event_to_json(event) {
+                json["secs"] = time.as_secs().into();
+                json["nanos"] = time.subsec_nanos().into();
+                string.push_str(&json.dump());
+                string.push('\n');
+            }
+        }
+        string
+    }
+
+    /// Decode a `Recording` produced by `Recording::to_json_lines`.
+    pub fn from_json_lines(string: &str) -> Result<Self, Error> {
+        let mut recording = Recording::new();
+        for (i, line) in string.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let json = try!(json::parse(line));
+            let secs = json["secs"].as_u64();
+            let nanos = json["nanos"].as_u32();
+            let event = event_from_json(&json);
+            match (secs, nanos, event) {
+                (Some(secs), Some(nanos), Some(event)) =>
+                    recording.push(Duration::new(secs, nanos), event),
+                _ => return Err(Error::InvalidEvent(i)),
+            }
+        }
+        Ok(recording)
+    }
+
+    /// Save the `Recording` to a file at the given path.
+    pub fn save<P>(&self, path: P) -> Result<(), Error>
+        where P: AsRef<std::path::Path>,
+    {
+        use std::io::Write;
+        let mut file = try!(std::fs::File::create(path));
+        try!(file.write_all(self.to_json_lines().as_bytes()));
+        Ok(())
+    }
+
+    /// Load a `Recording` from a file at the given path.
+    pub fn load<P>(path: P) -> Result<Self, Error>
+        where P: AsRef<std::path::Path>,
+    {
+        use std::io::Read;
+        let mut file = try!(std::fs::File::open(path));
+        let mut string = String::new();
+        try!(file.read_to_string(&mut string));
+        Recording::from_json_lines(&string)
+    }
+
+}
+
+impl Recorder {
+
+    /// Begin a new `Recording`, timestamping events relative to now.
+    pub fn new() -> Self {
+        Recorder {
+            start: Instant::now(),
+            recording: Recording::new(),
+        }
+    }
+
+    /// Append the given event to the `Recording`, timestamped with the current time.
+    pub fn record<E: Into<Event>>(&mut self, event: E) {
+        let time = Instant::now().duration_since(self.start);
+        self.recording.push(time, event);
+    }
+
+    /// The events recorded so far.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Stop recording, producing the `Recording`.
+    pub fn into_recording(self) -> Recording {
+        self.recording
+    }
+
+}
+
+impl Player {
+
+    /// Prepare to replay the given `Recording`.
+    ///
+    /// The fake clock begins at the moment the `Player` is created.
+    pub fn new(recording: Recording) -> Self {
+        Player {
+            recording: recording,
+            next: 0,
+            start: Instant::now(),
+        }
+    }
+
+    /// The time, as read from the recording, of the next event to be played.
+    pub fn next_time(&self) -> Option<Duration> {
+        self.recording.events.get(self.next).map(|&(time, _)| time)
+    }
+
+    /// Whether or not every event has been played.
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.recording.events.len()
+    }
+
+    /// Play the next event, first setting the `Ui`'s clock to the time at which it was recorded.
+    ///
+    /// Returns `false` if every event has already been played. Note that the `Ui`'s clock remains
+    /// set until `Ui::set_clock(None)` is called.
+    pub fn step(&mut self, ui: &mut Ui) -> bool {
+        let (time, event) = match self.recording.events.get(self.next) {
+            Some(&(time, ref event)) => (time, event.clone()),
+            None => return false,
+        };
+        self.next += 1;
+        ui.set_clock(Some(self.start + time));
+        match event {
+            Event::Raw(event) => ui.handle_event(event),
+            Event::Composition(composition) => ui.handle_composition(composition),
+        }
+        true
+    }
+
+    /// Play every event recorded up to and including the given time since the recording began.
+    ///
+    /// This may be called once per frame, between calls to `Ui::set_widgets`, so that the widget
+    /// events produced during the replay are delivered in much the same batches as they were
+    /// originally.
+    pub fn play_until(&mut self, ui: &mut Ui, time: Duration) {
+        while self.next_time().map_or(false, |next| next <= time) {
+            self.step(ui);
+        }
+    }
+
+    /// Play all remaining events.
+    pub fn play_all(&mut self, ui: &mut Ui) {
+        while self.step(ui) {}
+    }
+
+}
+
+
+impl From<event::Raw> for Event {
+    fn from(event: event::Raw) -> Self {
+        Event::Raw(event)
+    }
+}
+
+impl From<event::Composition> for Event {
+    fn from(composition: event::Composition) -> Self {
+        Event::Composition(composition)
+    }
+}
+
+
+/// Encode the given event as JSON, or `None` if the event cannot affect the `Ui`.
+fn event_to_json(event: &Event) -> Option<JsonValue> {
+    let event = match *event {
+        Event::Raw(ref event) => event,
+        Event::Composition(ref composition) => return Some(composition_to_json(composition)),
+    };
+    let input = match *event {
+        RawEvent::Render(ref args) => return Some(object!{
+            "type" => "render",
+            "ext_dt" => args.ext_dt,
+            "width" => args.width,
+            "height" => args.height,
+            "draw_width" => args.draw_width,
+            "draw_height" => args.draw_height
+        }),
+        RawEvent::Input(ref input) => input,
+        _ => return None,
+    };
+    let json = match *input {
+        Input::Press(button) => object!{
+            "type" => "press",
+            "button" => button_to_json(button)
+        },
+        Input::Release(button) => object!{
+            "type" => "release",
+            "button" => button_to_json(button)
+        },
+        Input::Move(Motion::MouseCursor(x, y)) => object!{
+            "type" => "mouse_cursor",
+            "xy" => array![x, y]
+        },
+        Input::Move(Motion::MouseRelative(x, y)) => object!{
+            "type" => "mouse_relative",
+            "xy" => array![x, y]
+        },
+        Input::Move(Motion::MouseScroll(x, y)) => object!{
+            "type" => "mouse_scroll",
+            "xy" => array![x, y]
+        },
+        Input::Move(Motion::ControllerAxis(args)) => object!{
+            "type" => "controller_axis",
+            "id" => args.id,
+            "axis" => args.axis,
+            "position" => args.position
+        },
+        Input::Move(Motion::Touch(args)) => object!{
+            "type" => "touch",
+            "device" => args.device,
+            "id" => args.id,
+            "xyz" => array![args.x, args.y, args.z],
+            "pressure" => array![args.px, args.py, args.pz],
+            "is_3d" => args.is_3d,
+            "phase" => match args.touch {
+                input::Touch::Start => "start",
+                input::Touch::Move => "move",
+                input::Touch::End => "end",
+                input::Touch::Cancel => "cancel",
+            }
+        },
+        Input::Text(ref string) => object!{
+            "type" => "text",
+            "string" => &string[..]
+        },
+        Input::Resize(w, h) => object!{
+            "type" => "resize",
+            "dim" => array![w, h]
+        },
+        Input::Focus(focused) => object!{
+            "type" => "focus",
+            "value" => focused
+        },
+        Input::Cursor(over_window) => object!{
+            "type" => "cursor",
+            "value" => over_window
+        },
+    };
+    Some(json)
+}
+
+/// Decode an event encoded by `event_to_json`.
+fn event_from_json(json: &JsonValue) -> Option<Event> {
+    let xy = |json: &JsonValue| match (json[0].as_f64(), json[1].as_f64()) {
+        (Some(x), Some(y)) => Some((x, y)),
+        _ => None,
+    };
+    let xyz = |json: &JsonValue| match (json[0].as_f64(), json[1].as_f64(), json[2].as_f64()) {
+        (Some(x), Some(y), Some(z)) => Some((x, y, z)),
+        _ => None,
+    };
+    let input = match json["type"].as_str() {
+        Some("composition") => return composition_from_json(json).map(Event::Composition),
+        Some("render") => {
+            let args = input::RenderArgs {
+                ext_dt: match json["ext_dt"].as_f64() { Some(dt) => dt, None => return None },
+                width: match json["width"].as_u32() { Some(w) => w, None => return None },
+                height: match json["height"].as_u32() { Some(h) => h, None => return None },
+                draw_width: match json["draw_width"].as_u32() { Some(w) => w, None => return None },
+                draw_height: match json["draw_height"].as_u32() { Some(h) => h, None => return None },
+            };
+            return Some(Event::Raw(RawEvent::Render(args)));
+        },
+        Some("press") => button_from_json(&json["button"]).map(Input::Press),
+        Some("release") => button_from_json(&json["button"]).map(Input::Release),
+        Some("mouse_cursor") =>
+            xy(&json["xy"]).map(|(x, y)| Input::Move(Motion::MouseCursor(x, y))),
+        Some("mouse_relative") =>
+            xy(&json["xy"]).map(|(x, y)| Input::Move(Motion::MouseRelative(x, y))),
+        Some("mouse_scroll") =>
+            xy(&json["xy"]).map(|(x, y)| Input::Move(Motion::MouseScroll(x, y))),
+        Some("controller_axis") => {
+            match (json["id"].as_i32(), json["axis"].as_u8(), json["position"].as_f64()) {
+                (Some(id), Some(axis), Some(position)) => {
+                    let args = input::ControllerAxisArgs { id: id, axis: axis, position: position };
+                    Some(Input::Move(Motion::ControllerAxis(args)))
+                },
+                _ => None,
+            }
+        },
+        Some("touch") => {
+            let phase = match json["phase"].as_str() {
+                Some("start") => input::Touch::Start,
+                Some("move") => input::Touch::Move,
+                Some("end") => input::Touch::End,
+                Some("cancel") => input::Touch::Cancel,
+                _ => return None,
+            };
+            let ids = (json["device"].as_i64(), json["id"].as_i64());
+            let vectors = (xyz(&json["xyz"]), xyz(&json["pressure"]));
+            match (ids, vectors, json["is_3d"].as_bool()) {
+                ((Some(device), Some(id)), (Some((x, y, z)), Some((px, py, pz))), Some(is_3d)) => {
+                    let args = input::TouchArgs {
+                        device: device,
+                        id: id,
+                        x: x,
+                        y: y,
+                        z: z,
+                        px: px,
+                        py: py,
+                        pz: pz,
+                        is_3d: is_3d,
+                        touch: phase,
+                    };
+                    Some(Input::Move(Motion::Touch(args)))
+                },
+                _ => None,
+            }
+        },
+        Some("text") => json["string"].as_str().map(|s| Input::Text(s.to_string())),
+        Some("resize") => match (json["dim"][0].as_u32(), json["dim"][1].as_u32()) {
+            (Some(w), Some(h)) => Some(Input::Resize(w, h)),
+            _ => None,
+        },
+        Some("focus") => json["value"].as_bool().map(Input::Focus),
+        Some("cursor") => json["value"].as_bool().map(Input::Cursor),
+        _ => None,
+    };
+    input.map(|input| Event::Raw(input.into()))
+}
+
+/// Encode the given input method composition as JSON.
+fn composition_to_json(composition: &event::Composition) -> JsonValue {
+    match *composition {
+        event::Composition::Start => object!{
+            "type" => "composition",
+            "phase" => "start"
+        },
+        event::Composition::Update(ref pre_edit) => object!{
+            "type" => "composition",
+            "phase" => "update",
+            "string" => &pre_edit.string[..],
+            "cursor" => pre_edit.cursor
+        },
+        event::Composition::Commit(ref string) => object!{
+            "type" => "composition",
+            "phase" => "commit",
+            "string" => &string[..]
+        },
+    }
+}
+
+/// Decode an input method composition encoded by `composition_to_json`.
+fn composition_from_json(json: &JsonValue) -> Option<event::Composition> {
+    match json["phase"].as_str() {
+        Some("start") => Some(event::Composition::Start),
+        Some("update") => {
+            let cursor = if json["cursor"].is_null() {
+                None
+            } else {
+                match json["cursor"].as_usize() {
+                    Some(cursor) => Some(cursor),
+                    None => return None,
+                }
+            };
+            json["string"].as_str().map(|string| {
+                let pre_edit = event::PreEdit { string: string.to_string(), cursor: cursor };
+                event::Composition::Update(pre_edit)
+            })
+        },
+        Some("commit") =>
+            json["string"].as_str().map(|string| event::Composition::Commit(string.to_string())),
+        _ => None,
+    }
+}
+
+/// Encode the given button as JSON.
+fn button_to_json(button: input::Button) -> JsonValue {
+    match button {
+        input::Button::Keyboard(key) => object!{ "keyboard" => u32::from(key) },
+        input::Button::Mouse(button) => object!{ "mouse" => u32::from(button) },
+        input::Button::Controller(button) => object!{
+            "controller" => array![button.id, button.button]
+        },
+    }
+}
+
+/// Decode a button encoded by `button_to_json`.
+fn button_from_json(json: &JsonValue) -> Option<input::Button> {
+    if let Some(key) = json["keyboard"].as_u32() {
+        return Some(input::Button::Keyboard(input::Key::from(key)));
+    }
+    if let Some(button) = json["mouse"].as_u32() {
+        return Some(input::Button::Mouse(input::MouseButton::from(button)));
+    }
+    let controller = &json["controller"];
+    match (controller[0].as_i32(), controller[1].as_u8()) {
+        (Some(id), Some(button)) =>
+            Some(input::Button::Controller(input::ControllerButton::new(id, button))),
+        _ => None,
+    }
+}
+
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<json::Error> for Error {
+    fn from(e: json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+impl std::error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::IO(ref e) => std::error::Error::description(e),
+            Error::Json(ref e) => std::error::Error::description(e),
+            Error::InvalidEvent(_) => "Some line of the recording did not describe an event.",
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        writeln!(f, "{}", std::error::Error::description(self))
+    }
+}
+
+
+#[test]
+fn recordings_should_survive_a_round_trip_through_json() {
+    use input::{Button, Key, MouseButton};
+    let mut recording = Recording::new();
+    recording.push(Duration::new(0, 0), event::render(0.016, 640, 480, 1.0));
+    recording.push(Duration::new(0, 5), Input::Move(Motion::MouseCursor(-10.5, 20.0)).into());
+    recording.push(Duration::new(1, 0), Input::Press(Button::Mouse(MouseButton::Left)).into());
+    recording.push(Duration::new(1, 250), Input::Release(Button::Keyboard(Key::Return)).into());
+    recording.push(Duration::new(2, 0), Input::Text("héllo".to_string()).into());
+    let touch = input::TouchArgs::new(3, 7, [0.25, -0.5], 0.75, input::Touch::Move);
+    recording.push(Duration::new(2, 10), Input::Move(Motion::Touch(touch)).into());
+    let pre_edit = event::PreEdit { string: "にほ".to_string(), cursor: Some(2) };
+    recording.push(Duration::new(3, 0), event::Composition::Start);
+    recording.push(Duration::new(3, 5), event::Composition::Update(pre_edit));
+    recording.push(Duration::new(3, 10), event::Composition::Commit("日本".to_string()));
+    let decoded = Recording::from_json_lines(&recording.to_json_lines()).unwrap();
+    assert_eq!(decoded, recording);
+}
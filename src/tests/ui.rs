@@ -11,6 +11,7 @@ use {
 use event::{self, Input, Motion};
 use input::{self, Button, Key, MouseButton};
 use input::keyboard::ModifierKey;
+use input::state::touch;
 use widget::{self, Index};
 use position::Point;
 
@@ -64,6 +65,11 @@ fn to_window_coordinates(xy: Point, ui: &Ui) -> Point {
     [x, y]
 }
 
+fn touch_screen(phase: input::Touch, id: i64, xy: Point, ui: &mut Ui) {
+    let args = input::TouchArgs::new(0, id, xy, 1.0, phase);
+    ui.handle_event(Input::Move(Motion::Touch(args)));
+}
+
 fn windowless_ui() -> Ui {
     UiBuilder::new().build()
 }
@@ -130,3 +136,693 @@ fn high_level_scroll_event_should_be_created_from_a_raw_mouse_scroll() {
         assert_eq!(expected_scroll, scroll);
     }
 }
+
+#[test]
+fn composition_commit_should_also_produce_a_text_event() {
+    let mut ui = windowless_ui();
+    let pre_edit = event::PreEdit { string: "に".to_string(), cursor: Some(1) };
+    ui.handle_composition(event::Composition::Update(pre_edit.clone()));
+    ui.handle_composition(event::Composition::Commit("日".to_string()));
+
+    let update = event::Ui::Composition(None, event::Composition::Update(pre_edit));
+    assert_event_was_pushed(&ui, update.into());
+    let commit = event::Ui::Composition(None, event::Composition::Commit("日".to_string()));
+    assert_event_was_pushed(&ui, commit.into());
+    let text = event::Text { string: "日".to_string(), modifiers: ModifierKey::default() };
+    assert_event_was_pushed(&ui, event::Ui::Text(None, text).into());
+}
+
+#[test]
+fn consecutive_clicks_should_produce_multi_click_events() {
+    let mut ui = windowless_ui();
+    left_click_mouse(&mut ui);
+    left_click_mouse(&mut ui);
+    left_click_mouse(&mut ui);
+
+    let modifiers = ModifierKey::default();
+    let double_click = event::DoubleClick {
+        button: MouseButton::Left,
+        xy: [0.0, 0.0],
+        modifiers: modifiers,
+    };
+    assert_event_was_pushed(&ui, event::Ui::DoubleClick(None, double_click).into());
+    for count in 2..4 {
+        let multi_click = event::MultiClick {
+            button: MouseButton::Left,
+            xy: [0.0, 0.0],
+            modifiers: modifiers,
+            count: count,
+        };
+        assert_event_was_pushed(&ui, event::Ui::MultiClick(None, multi_click).into());
+    }
+    assert_eq!(ui.global_input.click_count, 3);
+}
+
+#[test]
+fn tab_should_move_keyboard_capture_between_focusable_widgets() {
+    let mut ui = windowless_ui();
+
+    const FIRST_ID: widget::Id = widget::Id(0);
+    const SECOND_ID: widget::Id = widget::Id(1);
+    const UNFOCUSABLE_ID: widget::Id = widget::Id(2);
+
+    {
+        let ui = &mut ui.set_widgets();
+        // Instantiated first, but visited last due to its greater tab index.
+        widget::Rectangle::fill([10.0, 10.0]).tab_index(1).set(SECOND_ID, ui);
+        widget::Rectangle::fill([10.0, 10.0]).set(UNFOCUSABLE_ID, ui);
+        widget::Rectangle::fill([10.0, 10.0]).tab_index(0).set(FIRST_ID, ui);
+    }
+
+    let first = Index::Public(FIRST_ID);
+    let second = Index::Public(SECOND_ID);
+    let press_tab = |ui: &mut Ui| ui.handle_event(Input::Press(Button::Keyboard(Key::Tab)));
+
+    press_tab(&mut ui);
+    assert_eq!(ui.global_input.current.widget_capturing_keyboard, Some(first));
+    assert_event_was_pushed(&ui, event::Ui::WidgetCapturesKeyboard(first).into());
+
+    // The tab entered by a `Tab` that moves focus is not delivered to the newly focused widget.
+    let enter_tab = |ui: &mut Ui| ui.handle_event(Input::Text("\t".to_string()));
+    let is_text = |event: &event::Ui| match *event { event::Ui::Text(..) => true, _ => false };
+    enter_tab(&mut ui);
+    assert!(!ui.global_input.events().ui().any(is_text));
+
+    press_tab(&mut ui);
+    assert_eq!(ui.global_input.current.widget_capturing_keyboard, Some(second));
+    assert_event_was_pushed(&ui, event::Ui::WidgetUncapturesKeyboard(first).into());
+
+    // Focus wraps around to the first widget.
+    press_tab(&mut ui);
+    assert_eq!(ui.global_input.current.widget_capturing_keyboard, Some(first));
+
+    // `Shift+Tab` moves focus backwards.
+    ui.handle_event(Input::Press(Button::Keyboard(Key::LShift)));
+    press_tab(&mut ui);
+    assert_eq!(ui.global_input.current.widget_capturing_keyboard, Some(second));
+
+    ui.set_keyboard_focus(None);
+    assert_eq!(ui.global_input.current.widget_capturing_keyboard, None);
+    assert_event_was_pushed(&ui, event::Ui::WidgetUncapturesKeyboard(second).into());
+    ui.handle_event(Input::Release(Button::Keyboard(Key::LShift)));
+
+    // A `TextEdit` that accepts tabs keeps keyboard focus upon `Tab` and receives the tab itself.
+    const TEXT_EDIT_ID: widget::Id = widget::Id(3);
+    let font_path = concat!(env!("CARGO_MANIFEST_DIR"),
+                            "/assets/fonts/NotoSans/NotoSans-Regular.ttf");
+    ui.fonts.insert_from_file(font_path).unwrap();
+    let text_edit = Index::Public(TEXT_EDIT_ID);
+    ui.set_keyboard_focus(Some(text_edit));
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([10.0, 10.0]).tab_index(1).set(SECOND_ID, ui);
+        widget::Rectangle::fill([10.0, 10.0]).tab_index(0).set(FIRST_ID, ui);
+        widget::TextEdit::new("")
+            .accepts_tab(true)
+            .w_h(100.0, 100.0)
+            .set(TEXT_EDIT_ID, ui);
+    }
+    press_tab(&mut ui);
+    assert_eq!(ui.global_input.current.widget_capturing_keyboard, Some(text_edit));
+    enter_tab(&mut ui);
+    let text = event::Text { string: "\t".to_string(), modifiers: ModifierKey::default() };
+    assert_event_was_pushed(&ui, event::Ui::Text(Some(text_edit), text).into());
+}
+
+#[test]
+fn tab_should_enter_a_tab_when_there_is_no_widget_to_move_focus_to() {
+    let mut ui = windowless_ui();
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Tab)));
+    ui.handle_event(Input::Text("\t".to_string()));
+    let text = event::Text { string: "\t".to_string(), modifiers: ModifierKey::default() };
+    assert_event_was_pushed(&ui, event::Ui::Text(None, text).into());
+}
+
+#[test]
+fn lifting_a_finger_that_has_not_moved_should_produce_a_tap() {
+    let mut ui = windowless_ui();
+    touch_screen(input::Touch::Start, 0, [0.0, 0.0], &mut ui);
+    touch_screen(input::Touch::End, 0, [2.0, 1.0], &mut ui);
+
+    let tap = event::Tap { id: touch::Id(0), xy: [2.0, 1.0] };
+    assert_event_was_pushed(&ui, event::Ui::Tap(None, tap).into());
+    assert!(ui.global_input.touch.is_empty());
+}
+
+#[test]
+fn moving_a_single_finger_should_produce_a_pan() {
+    let mut ui = windowless_ui();
+    touch_screen(input::Touch::Start, 0, [0.0, 0.0], &mut ui);
+    touch_screen(input::Touch::Move, 0, [20.0, 0.0], &mut ui);
+    touch_screen(input::Touch::End, 0, [20.0, 0.0], &mut ui);
+
+    let pan = event::Pan {
+        id: touch::Id(0),
+        origin: [0.0, 0.0],
+        from: [0.0, 0.0],
+        to: [20.0, 0.0],
+        delta_xy: [20.0, 0.0],
+        total_delta_xy: [20.0, 0.0],
+    };
+    assert_event_was_pushed(&ui, event::Ui::Pan(None, pan).into());
+    let tapped = ui.global_input.events().any(|event| match *event {
+        event::Event::Ui(event::Ui::Tap(_, _)) => true,
+        _ => false,
+    });
+    assert!(!tapped);
+}
+
+#[test]
+fn moving_two_fingers_apart_should_produce_a_pinch() {
+    let mut ui = windowless_ui();
+    touch_screen(input::Touch::Start, 0, [-10.0, 0.0], &mut ui);
+    touch_screen(input::Touch::Start, 1, [10.0, 0.0], &mut ui);
+    touch_screen(input::Touch::Move, 1, [30.0, 0.0], &mut ui);
+
+    let pinch = event::Pinch { centre: [10.0, 0.0], scale: 2.0 };
+    assert_event_was_pushed(&ui, event::Ui::Pinch(None, pinch).into());
+}
+
+#[test]
+fn the_primary_finger_should_emulate_the_mouse_when_enabled() {
+    let mut ui = UiBuilder::new().touch_emulates_mouse(true).build();
+    touch_screen(input::Touch::Start, 0, [5.0, 5.0], &mut ui);
+    assert_eq!(ui.global_input.current.mouse.xy, [5.0, 5.0]);
+    assert!(ui.global_input.current.mouse.buttons.left().is_down());
+
+    // A second finger does not affect the mouse.
+    touch_screen(input::Touch::Start, 1, [50.0, 50.0], &mut ui);
+    touch_screen(input::Touch::End, 1, [50.0, 50.0], &mut ui);
+    assert_eq!(ui.global_input.current.mouse.xy, [5.0, 5.0]);
+    assert!(ui.global_input.current.mouse.buttons.left().is_down());
+
+    touch_screen(input::Touch::End, 0, [5.0, 5.0], &mut ui);
+    assert!(ui.global_input.current.mouse.buttons.left().is_up());
+    let click = event::Click {
+        button: MouseButton::Left,
+        xy: [5.0, 5.0],
+        modifiers: ModifierKey::default(),
+    };
+    assert_event_was_pushed(&ui, event::Ui::Click(None, click).into());
+}
+
+#[test]
+fn resting_the_mouse_over_a_widget_should_produce_enter_hover_and_leave_events() {
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const RECTANGLE_ID: widget::Id = widget::Id(0);
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([100.0, 100.0]).x_y(0.0, 0.0).set(RECTANGLE_ID, ui);
+    }
+    let rectangle = Index::Public(RECTANGLE_ID);
+
+    move_mouse_to_abs_coordinates(10.0, 10.0, &mut ui);
+    assert_event_was_pushed(&ui, event::Ui::MouseEnter(rectangle).into());
+
+    // The mouse has not yet rested for the `hover_delay`.
+    ui.handle_event(event::render(0.1, 250, 300, 1.0));
+    let has_hovered = |ui: &Ui| ui.global_input.events().any(|event| match *event {
+        event::Event::Ui(event::Ui::Hover(_, _)) => true,
+        _ => false,
+    });
+    assert!(!has_hovered(&ui));
+
+    ui.handle_event(event::render(1.0, 250, 300, 1.0));
+    let hover = event::Hover { xy: [10.0, 10.0], modifiers: ModifierKey::default() };
+    assert_event_was_pushed(&ui, event::Ui::Hover(Some(rectangle), hover).into());
+
+    move_mouse_to_abs_coordinates(100.0, 100.0, &mut ui);
+    assert_event_was_pushed(&ui, event::Ui::MouseLeave(rectangle).into());
+}
+
+#[test]
+fn payloads_should_be_dropped_upon_and_accepted_by_the_widget_under_the_mouse() {
+    use input::drag_and_drop::Payload;
+
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const SOURCE_ID: widget::Id = widget::Id(0);
+    const TARGET_ID: widget::Id = widget::Id(1);
+    fn set_widgets(ui: &mut ::UiCell) {
+        widget::Rectangle::fill([100.0, 100.0]).x_y(-60.0, 0.0).set(SOURCE_ID, ui);
+        widget::Rectangle::fill([100.0, 100.0]).x_y(60.0, 0.0).set(TARGET_ID, ui);
+    }
+    set_widgets(&mut ui.set_widgets());
+    let (source, target) = (Index::Public(SOURCE_ID), Index::Public(TARGET_ID));
+
+    // Start dragging from the source while the left mouse button is down.
+    move_mouse_to_abs_coordinates(-60.0, 0.0, &mut ui);
+    press_mouse_button(MouseButton::Left, &mut ui);
+    {
+        let ui = &mut ui.set_widgets();
+        set_widgets(ui);
+        ui.start_drag_and_drop(source, Payload::new("number", 42u32));
+    }
+    assert_eq!(ui.drag_and_drop_payload().map(|payload| payload.kind()), Some("number"));
+
+    move_mouse_to_abs_coordinates(60.0, 0.0, &mut ui);
+    let dnd = event::DragAndDrop {
+        source: source,
+        kind: "number",
+        xy: [60.0, 0.0],
+        modifiers: ModifierKey::default(),
+    };
+    assert_event_was_pushed(&ui, event::Ui::DragEnter(target, dnd).into());
+    assert_event_was_pushed(&ui, event::Ui::DragOver(target, dnd).into());
+
+    release_mouse_button(MouseButton::Left, &mut ui);
+    assert_event_was_pushed(&ui, event::Ui::Drop(target, dnd).into());
+
+    // Only the target may accept the payload.
+    {
+        let ui = &mut ui.set_widgets();
+        set_widgets(ui);
+        assert!(ui.accept_drop(source).is_none());
+        let payload = ui.accept_drop(target).unwrap();
+        assert_eq!(*payload.downcast::<u32>().unwrap(), 42);
+    }
+    assert!(ui.drag_and_drop_payload().is_none());
+    let drag_end = event::DragEnd { target: Some(target), accepted: true };
+    assert_event_was_pushed(&ui, event::Ui::DragEnd(source, drag_end).into());
+}
+
+#[test]
+fn shortcuts_should_trigger_unless_the_key_is_consumed_by_the_capturing_widget() {
+    use input::keyboard::{CTRL, NO_MODIFIER};
+    use input::shortcut::{Id, Shortcut};
+
+    let mut ui = windowless_ui();
+    let save = Id(0);
+    let find = Id(1);
+    ui.register_shortcut(save, Shortcut::new(CTRL, Key::K).then(CTRL, Key::S));
+    ui.register_shortcut(find, Shortcut::new(NO_MODIFIER, Key::F));
+
+    const TEXT_ID: widget::Id = widget::Id(0);
+    fn set_widgets(ui: &mut Ui) {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([10.0, 10.0]).tab_index(0).set(TEXT_ID, ui);
+        ui.consume_text_input(TEXT_ID);
+    }
+    set_widgets(&mut ui);
+    let press = |key: Key, ui: &mut Ui| ui.handle_event(Input::Press(Button::Keyboard(key)));
+    let release = |key: Key, ui: &mut Ui| ui.handle_event(Input::Release(Button::Keyboard(key)));
+
+    press(Key::F, &mut ui);
+    assert!(ui.shortcut_triggered(find));
+
+    // Plain character keys are consumed while the widget entering text captures the keyboard.
+    ui.set_keyboard_focus(Some(Index::Public(TEXT_ID)));
+    set_widgets(&mut ui);
+    press(Key::F, &mut ui);
+    assert!(!ui.shortcut_triggered(find));
+
+    // Both combos of a chord must be pressed before it is triggered.
+    press(Key::LCtrl, &mut ui);
+    press(Key::K, &mut ui);
+    assert!(!ui.shortcut_triggered(save));
+    press(Key::S, &mut ui);
+    release(Key::LCtrl, &mut ui);
+    assert!(ui.shortcut_triggered(save));
+    assert_event_was_pushed(&ui, event::Ui::Shortcut(save).into());
+}
+
+#[test]
+fn controller_should_move_keyboard_focus_to_the_nearest_widget_in_each_direction() {
+    use input::ControllerButton;
+
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const LEFT_ID: widget::Id = widget::Id(0);
+    const RIGHT_ID: widget::Id = widget::Id(1);
+    const FAR_RIGHT_ID: widget::Id = widget::Id(2);
+    const BELOW_ID: widget::Id = widget::Id(3);
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([10.0, 10.0]).x_y(-50.0, 0.0).tab_index(0).set(LEFT_ID, ui);
+        widget::Rectangle::fill([10.0, 10.0]).x_y(100.0, 0.0).tab_index(1).set(FAR_RIGHT_ID, ui);
+        widget::Rectangle::fill([10.0, 10.0]).x_y(40.0, 20.0).tab_index(2).set(RIGHT_ID, ui);
+        widget::Rectangle::fill([10.0, 10.0]).x_y(-50.0, -60.0).tab_index(3).set(BELOW_ID, ui);
+    }
+    let press = |button: u8, ui: &mut Ui| {
+        let button = ControllerButton::new(0, button);
+        ui.handle_event(Input::Press(Button::Controller(button)));
+        ui.handle_event(Input::Release(Button::Controller(button)));
+    };
+    let focused = |ui: &Ui| ui.global_input.current.widget_capturing_keyboard;
+    const DPAD_DOWN: u8 = 12;
+    const DPAD_RIGHT: u8 = 14;
+
+    // With nothing focused, the first focusable widget receives focus.
+    press(DPAD_RIGHT, &mut ui);
+    assert_eq!(focused(&ui), Some(Index::Public(LEFT_ID)));
+
+    press(DPAD_RIGHT, &mut ui);
+    assert_eq!(focused(&ui), Some(Index::Public(RIGHT_ID)));
+    press(DPAD_RIGHT, &mut ui);
+    assert_eq!(focused(&ui), Some(Index::Public(FAR_RIGHT_ID)));
+
+    // There is nothing further to the right.
+    press(DPAD_RIGHT, &mut ui);
+    assert_eq!(focused(&ui), Some(Index::Public(FAR_RIGHT_ID)));
+
+    press(DPAD_DOWN, &mut ui);
+    assert_eq!(focused(&ui), Some(Index::Public(BELOW_ID)));
+}
+
+#[test]
+fn replayed_clicks_should_be_interpreted_with_the_recorded_timing() {
+    use record::{self, Player, Recording};
+    use std::time::Duration;
+
+    let mut ui = windowless_ui();
+    let press: event::Raw = Input::Press(Button::Mouse(MouseButton::Left)).into();
+    let release: event::Raw = Input::Release(Button::Mouse(MouseButton::Left)).into();
+
+    // Each event given to the `Ui` while recording is recorded.
+    ui.start_recording();
+    ui.handle_event(press.clone());
+    ui.handle_event(release.clone());
+    let recorded = ui.stop_recording().unwrap();
+    assert_eq!(recorded.len(), 2);
+    assert_eq!(recorded.events()[0].1, record::Event::Raw(press.clone()));
+
+    // A double click followed by a click long after.
+    let mut recording = Recording::new();
+    for &millis in &[0, 100, 2_000] {
+        recording.push(Duration::from_millis(millis), press.clone());
+        recording.push(Duration::from_millis(millis), release.clone());
+    }
+    let mut ui = windowless_ui();
+    let mut player = Player::new(recording);
+    let has_double_clicked = |ui: &Ui| ui.global_input.events().any(|event| match *event {
+        event::Event::Ui(event::Ui::DoubleClick(_, _)) => true,
+        _ => false,
+    });
+
+    // Replaying the clicks immediately still respects the recorded timing.
+    player.play_until(&mut ui, Duration::from_millis(100));
+    assert!(has_double_clicked(&ui));
+    ui.set_widgets();
+
+    player.play_all(&mut ui);
+    assert!(player.is_finished());
+    assert!(!has_double_clicked(&ui));
+}
+
+#[test]
+fn mouse_cursor_should_follow_the_widget_capturing_or_under_the_mouse() {
+    use cursor::MouseCursor;
+
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const SLIDER_ID: widget::Id = widget::Id(0);
+    fn set_widgets(ui: &mut Ui) {
+        let ui = &mut ui.set_widgets();
+        widget::Slider::new(0.5, 0.0, 1.0).w_h(200.0, 30.0).x_y(0.0, 0.0).set(SLIDER_ID, ui);
+    }
+
+    set_widgets(&mut ui);
+    assert_eq!(ui.mouse_cursor(), MouseCursor::Arrow);
+
+    move_mouse_to_abs_coordinates(10.0, 0.0, &mut ui);
+    set_widgets(&mut ui);
+    assert_eq!(ui.mouse_cursor(), MouseCursor::ResizeHorizontal);
+
+    // The cursor remains while the slider captures the mouse, even once it leaves the slider.
+    press_mouse_button(MouseButton::Left, &mut ui);
+    move_mouse_to_abs_coordinates(10.0, 100.0, &mut ui);
+    set_widgets(&mut ui);
+    assert_eq!(ui.mouse_cursor(), MouseCursor::ResizeHorizontal);
+
+    release_mouse_button(MouseButton::Left, &mut ui);
+    set_widgets(&mut ui);
+    assert_eq!(ui.mouse_cursor(), MouseCursor::Arrow);
+}
+
+#[test]
+fn unhandled_events_should_bubble_up_to_each_ancestor_within_the_same_frame() {
+    let mut ui = UiBuilder::new().bubble_events(true).build();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const OUTER_ID: widget::Id = widget::Id(0);
+    const INNER_ID: widget::Id = widget::Id(1);
+    const RECTANGLE_ID: widget::Id = widget::Id(2);
+    fn set_widgets(ui: &mut Ui) -> (Vec<event::Bubble>, Vec<event::Bubble>) {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().w_h(200.0, 200.0).x_y(0.0, 0.0).set(OUTER_ID, ui);
+        widget::Canvas::new().w_h(100.0, 100.0).middle_of(OUTER_ID).set(INNER_ID, ui);
+        widget::Rectangle::fill([50.0, 50.0]).middle_of(INNER_ID).set(RECTANGLE_ID, ui);
+        // The inner canvas handles the clicks that bubble up from the rectangle.
+        let inner_bubbles: Vec<_> = ui.widget_input(INNER_ID).bubbles().collect();
+        for bubble in &inner_bubbles {
+            if let event::Widget::Click(_) = *bubble.event {
+                ui.set_event_handled(INNER_ID, bubble.clone());
+            }
+        }
+        let outer_bubbles = ui.widget_input(OUTER_ID).bubbles().collect();
+        (inner_bubbles, outer_bubbles)
+    }
+    let rectangle = Index::Public(RECTANGLE_ID);
+
+    set_widgets(&mut ui);
+    move_mouse_to_abs_coordinates(0.0, 0.0, &mut ui);
+    left_click_mouse(&mut ui);
+
+    // The rectangle does not handle its click, so the click bubbles up to the inner canvas.
+    let (inner_bubbles, outer_bubbles) = set_widgets(&mut ui);
+    assert!(inner_bubbles.iter().any(|bubble| bubble.source == rectangle && match *bubble.event {
+        event::Widget::Click(_) => true,
+        _ => false,
+    }));
+
+    // The inner canvas handled the click, so only the press and release continue on to the outer
+    // canvas during the same call to `set_widgets`.
+    assert_eq!(outer_bubbles.len(), 2);
+    assert!(outer_bubbles.iter().all(|bubble| bubble.source == rectangle && match *bubble.event {
+        event::Widget::Press(_) | event::Widget::Release(_) => true,
+        _ => false,
+    }));
+
+    // Nothing bubbles during the following call to `set_widgets`.
+    let (inner_bubbles, outer_bubbles) = set_widgets(&mut ui);
+    assert!(inner_bubbles.is_empty());
+    assert!(outer_bubbles.is_empty());
+}
+
+#[test]
+fn events_should_not_bubble_unless_enabled() {
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const RECTANGLE_ID: widget::Id = widget::Id(1);
+    fn set_widgets(ui: &mut Ui) -> Vec<event::Bubble> {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().w_h(200.0, 200.0).x_y(0.0, 0.0).set(CANVAS_ID, ui);
+        widget::Rectangle::fill([50.0, 50.0]).middle_of(CANVAS_ID).set(RECTANGLE_ID, ui);
+        let bubbles = ui.widget_input(CANVAS_ID).bubbles().collect();
+        bubbles
+    }
+
+    set_widgets(&mut ui);
+    move_mouse_to_abs_coordinates(0.0, 0.0, &mut ui);
+    left_click_mouse(&mut ui);
+    assert!(set_widgets(&mut ui).is_empty());
+}
+
+#[test]
+fn events_handled_by_a_button_should_not_bubble_up_to_its_canvas() {
+    let mut ui = UiBuilder::new().bubble_events(true).build();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const BUTTON_ID: widget::Id = widget::Id(1);
+    fn set_widgets(ui: &mut Ui) -> (u16, Vec<event::Bubble>) {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().w_h(200.0, 200.0).x_y(0.0, 0.0).set(CANVAS_ID, ui);
+        let times_clicked = widget::Button::new()
+            .w_h(50.0, 50.0)
+            .middle_of(CANVAS_ID)
+            .set(BUTTON_ID, ui)
+            .0;
+        (times_clicked, ui.widget_input(CANVAS_ID).bubbles().collect())
+    }
+
+    set_widgets(&mut ui);
+    move_mouse_to_abs_coordinates(0.0, 0.0, &mut ui);
+    left_click_mouse(&mut ui);
+
+    // The button acts upon the click, so neither the click nor its press and release bubble up.
+    let (times_clicked, bubbles) = set_widgets(&mut ui);
+    assert_eq!(times_clicked, 1);
+    assert!(bubbles.is_empty());
+}
+
+#[test]
+fn key_presses_whose_commands_have_no_effect_should_bubble_up_from_a_text_edit() {
+    let mut ui = UiBuilder::new().bubble_events(true).build();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+    let font_path = concat!(env!("CARGO_MANIFEST_DIR"),
+                            "/assets/fonts/NotoSans/NotoSans-Regular.ttf");
+    ui.fonts.insert_from_file(font_path).unwrap();
+
+    const CANVAS_ID: widget::Id = widget::Id(0);
+    const TEXT_EDIT_ID: widget::Id = widget::Id(1);
+    fn set_widgets(ui: &mut Ui, text: &mut String) -> Vec<event::Bubble> {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().w_h(200.0, 200.0).x_y(0.0, 0.0).set(CANVAS_ID, ui);
+        let maybe_new_text = widget::TextEdit::new(text)
+            .w_h(100.0, 50.0)
+            .middle_of(CANVAS_ID)
+            .set(TEXT_EDIT_ID, ui);
+        if let Some(new_text) = maybe_new_text {
+            *text = new_text;
+        }
+        let bubbles = ui.widget_input(CANVAS_ID).bubbles().collect();
+        bubbles
+    }
+    let text_edit = Index::Public(TEXT_EDIT_ID);
+    let press_left = |ui: &mut Ui| ui.handle_event(Input::Press(Button::Keyboard(Key::Left)));
+    let mut text = String::new();
+
+    ui.set_keyboard_focus(Some(text_edit));
+    set_widgets(&mut ui, &mut text);
+
+    // The cursor is already at the start of the text, so moving it left has no effect.
+    press_left(&mut ui);
+    let bubbles = set_widgets(&mut ui, &mut text);
+    assert!(bubbles.iter().any(|bubble| bubble.source == text_edit && match *bubble.event {
+        event::Widget::Press(ref press) => press.button == event::Button::Keyboard(Key::Left),
+        _ => false,
+    }));
+
+    // Entered text and a cursor that moves are both handled by the `TextEdit`.
+    ui.handle_event(Input::Text("a".to_string()));
+    assert!(set_widgets(&mut ui, &mut text).is_empty());
+    assert_eq!(text, "a");
+    press_left(&mut ui);
+    assert!(set_widgets(&mut ui, &mut text).is_empty());
+}
+
+#[test]
+fn escape_should_cancel_the_drag_and_restore_the_pre_drag_value() {
+    use widget::range_slider::Edge;
+
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const RANGE_SLIDER_ID: widget::Id = widget::Id(0);
+    fn set_widgets(ui: &mut Ui, range: &mut (f64, f64)) {
+        let ui = &mut ui.set_widgets();
+        let events = widget::RangeSlider::new(range.0, range.1, 0.0, 1.0)
+            .w_h(200.0, 30.0)
+            .x_y(0.0, 0.0)
+            .set(RANGE_SLIDER_ID, ui);
+        for (edge, value) in events {
+            match edge {
+                Edge::Start => range.0 = value,
+                Edge::End => range.1 = value,
+            }
+        }
+    }
+    let range_slider = Index::Public(RANGE_SLIDER_ID);
+    let mut range = (0.2, 0.6);
+
+    // Drag the handle of the range, carrying the mouse beyond the slider.
+    set_widgets(&mut ui, &mut range);
+    move_mouse_to_abs_coordinates(-20.0, 0.0, &mut ui);
+    press_mouse_button(MouseButton::Left, &mut ui);
+    set_widgets(&mut ui, &mut range);
+    move_mouse_to_abs_coordinates(10.0, 100.0, &mut ui);
+    set_widgets(&mut ui, &mut range);
+    assert!(range.0 > 0.2 && range.1 > 0.6);
+
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Escape)));
+    let drag_cancelled = event::DragCancelled {
+        button: MouseButton::Left,
+        modifiers: ModifierKey::default(),
+    };
+    let drag_cancelled_event = event::Ui::DragCancelled(Some(range_slider), drag_cancelled);
+    assert_event_was_pushed(&ui, drag_cancelled_event.into());
+    assert_event_was_pushed(&ui, event::Ui::WidgetUncapturesMouse(range_slider).into());
+    assert!(ui.global_input.current.mouse.buttons.left().is_up());
+    set_widgets(&mut ui, &mut range);
+    assert_eq!(range, (0.2, 0.6));
+
+    // Moving the mouse no longer drags the range, and releasing the button does not click.
+    move_mouse_to_abs_coordinates(40.0, 100.0, &mut ui);
+    release_mouse_button(MouseButton::Left, &mut ui);
+    let is_drag_or_click = |event: &event::Ui| match *event {
+        event::Ui::Drag(..) | event::Ui::Click(..) => true,
+        _ => false,
+    };
+    assert!(!ui.global_input.events().ui().any(is_drag_or_click));
+    set_widgets(&mut ui, &mut range);
+    assert_eq!(range, (0.2, 0.6));
+}
+
+#[test]
+fn escape_should_not_cancel_a_mouse_button_that_is_held_without_dragging() {
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const BUTTON_ID: widget::Id = widget::Id(0);
+    fn set_widgets(ui: &mut Ui) -> u16 {
+        let ui = &mut ui.set_widgets();
+        widget::Button::new().w_h(100.0, 100.0).x_y(0.0, 0.0).set(BUTTON_ID, ui).0
+    }
+
+    set_widgets(&mut ui);
+    move_mouse_to_abs_coordinates(0.0, 0.0, &mut ui);
+    press_mouse_button(MouseButton::Left, &mut ui);
+    set_widgets(&mut ui);
+
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Escape)));
+    let is_drag_cancelled = |event: &event::Ui| match *event {
+        event::Ui::DragCancelled(..) => true,
+        _ => false,
+    };
+    assert!(!ui.global_input.events().ui().any(is_drag_cancelled));
+    assert!(ui.global_input.current.mouse.buttons.left().is_down());
+
+    // Releasing the button still clicks it.
+    release_mouse_button(MouseButton::Left, &mut ui);
+    assert_eq!(set_widgets(&mut ui), 1);
+}
+
+#[test]
+fn blank_text_box_with_a_filter_should_not_be_flagged_as_invalid() {
+    use widget::text_box::{Event, Filter};
+
+    let mut ui = windowless_ui();
+    ui.win_w = 250.0;
+    ui.win_h = 300.0;
+
+    const TEXT_BOX_ID: widget::Id = widget::Id(0);
+    let events = {
+        let ui = &mut ui.set_widgets();
+        widget::TextBox::new("")
+            .filter(Filter::Integer)
+            .w_h(100.0, 30.0)
+            .set(TEXT_BOX_ID, ui)
+    };
+    let validity: Vec<bool> = events.iter()
+        .filter_map(|event| match *event { Event::Validity(is_valid) => Some(is_valid), _ => None })
+        .collect();
+    assert_eq!(validity, vec![true]);
+}
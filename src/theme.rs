@@ -3,7 +3,7 @@
 //!
 
 use Scalar;
-use color::{Color, BLACK, WHITE};
+use color::{self, Color, BLACK, WHITE};
 use position::{Align, Direction, Padding, Position};
 use std;
 use std::any::Any;
@@ -34,6 +34,10 @@ pub struct Theme {
     pub label_color: Color,
     /// The `Id` of the default font used for text widgets when one is not specified.
     pub font_id: Option<text::font::Id>,
+    /// The `Id` of the default font used for code and other text that should be monospaced.
+    ///
+    /// If this field is `None`, `font_id` is used instead.
+    pub monospace_font_id: Option<text::font::Id>,
     /// A default "large" font size.
     pub font_size_large: u32,
     /// A default "medium" font size.
@@ -48,6 +52,31 @@ pub struct Theme {
     /// Once the `Duration` that separates two consecutive `Click`s is greater than this value, a
     /// `DoubleClick` event will no longer be generated.
     pub double_click_threshold: std::time::Duration,
+    /// The `Duration` for which the mouse must rest over a widget without moving before a `Hover`
+    /// event is generated.
+    pub hover_delay: std::time::Duration,
+    /// The distance that a finger must move from the point at which it first touched the screen
+    /// before it is considered to `Pan` rather than `Tap` or `LongPress`.
+    pub touch_drag_threshold: Scalar,
+    /// The `Duration` for which a finger must be held upon the screen without moving before a
+    /// `LongPress` event is generated.
+    pub long_press_duration: std::time::Duration,
+    /// The color of the ring drawn around the focusable widget that captures the keyboard.
+    pub focus_ring_color: Color,
+    /// The width of the ring drawn around the focusable widget that captures the keyboard.
+    ///
+    /// A width of `0.0` disables the focus ring.
+    pub focus_ring_width: Scalar,
+    /// The key bindings used by text editing widgets.
+    ///
+    /// If this field is `None`, the preset for the target platform is used.
+    pub key_bindings: Option<widget::text_edit::KeyBindings>,
+    /// How scrollable widgets animate towards their target offset and carry flings.
+    ///
+    /// By default this is `widget::scroll::Animation::instant()`, applying each scroll
+    /// immediately. Animation is opt-in, as it is stepped by the time reported by the `Render`
+    /// events passed to `Ui::handle_event`. Without them, scrolling would never progress.
+    pub scroll_animation: widget::scroll::Animation,
 }
 
 /// The defaults for a specific widget.
@@ -92,18 +121,27 @@ impl Theme {
             border_width: 1.0,
             label_color: BLACK,
             font_id: None,
+            monospace_font_id: None,
             font_size_large: 26,
             font_size_medium: 18,
             font_size_small: 12,
             widget_styling: std::collections::HashMap::new(),
             mouse_drag_threshold: 0.0,
             double_click_threshold: std::time::Duration::from_millis(500),
+            hover_delay: std::time::Duration::from_millis(600),
+            touch_drag_threshold: 10.0,
+            long_press_duration: std::time::Duration::from_millis(500),
+            focus_ring_color: color::rgb(0.5, 0.75, 1.0),
+            focus_ring_width: 2.0,
+            key_bindings: None,
+            scroll_animation: widget::scroll::Animation::instant(),
         }
     }
 
     /// Converts this **Theme** into a **JsonValue** representing it.
     /// font_id's and any references to widget id's are left out because they don't make sense to
-    /// store between applications and sessions.
+    /// store between applications and sessions. key_bindings and the scroll_animation are also
+    /// left out.
     pub fn into_json(self) -> JsonValue {
         object!{
             "name" => self.name,
@@ -122,7 +160,12 @@ impl Theme {
             "font_size_medium" => self.font_size_medium,
             "font_size_small" => self.font_size_small,
             "mouse_drag_threshold" => self.mouse_drag_threshold,
-            "double_click_threshold" => (self.double_click_threshold.as_secs() as u64 * 1000u64) + (self.double_click_threshold.subsec_nanos() as u64 / 1_000_000u64)
+            "double_click_threshold" => (self.double_click_threshold.as_secs() as u64 * 1000u64) + (self.double_click_threshold.subsec_nanos() as u64 / 1_000_000u64),
+            "hover_delay" => (self.hover_delay.as_secs() as u64 * 1000u64) + (self.hover_delay.subsec_nanos() as u64 / 1_000_000u64),
+            "touch_drag_threshold" => self.touch_drag_threshold,
+            "long_press_duration" => (self.long_press_duration.as_secs() as u64 * 1000u64) + (self.long_press_duration.subsec_nanos() as u64 / 1_000_000u64),
+            "focus_ring_color" => self.focus_ring_color.to_fsa().to_vec(),
+            "focus_ring_width" => self.focus_ring_width
         }
     }
 
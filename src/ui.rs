@@ -1,8 +1,12 @@
+use clipboard::{self, Clipboard};
 use color::Color;
+use cursor::MouseCursor;
 use event;
 use graph::{self, Graph, NodeIndex};
 use input;
-use position::{Align, Direction, Dimensions, Padding, Place, Point, Position, Range, Rect, Scalar};
+use position::{Align, Axis, Direction, Dimensions, Padding, Place, Point, Position, Range, Rect,
+               Scalar};
+use record;
 use render;
 use std;
 use text;
@@ -29,7 +33,24 @@ pub struct UiBuilder {
     ///
     /// If this field is `None` when `build` is called, these collections will be initialised with
     /// no pre-reserved size and will instead grow organically as needed.
-    pub maybe_widgets_capacity: Option<usize>
+    pub maybe_widgets_capacity: Option<usize>,
+    /// The clipboard used by text widgets for copy, cut and paste.
+    ///
+    /// If this field is `None` when `build` is called, an in-memory `clipboard::Memory` will be
+    /// used.
+    pub maybe_clipboard: Option<Box<Clipboard>>,
+    /// Whether or not the primary finger touching the screen should drive the mouse.
+    ///
+    /// See the `UiBuilder::touch_emulates_mouse` method for details. The default is `false`.
+    pub touch_emulates_mouse: bool,
+    /// The mapping of controller buttons and axes to navigation between widgets.
+    ///
+    /// The default is `input::controller::Bindings::default`.
+    pub controller_bindings: input::controller::Bindings,
+    /// Whether or not events left unhandled by a widget bubble up to its ancestors.
+    ///
+    /// See the `UiBuilder::bubble_events` method for details. The default is `false`.
+    pub bubble_events: bool,
 }
 
 /// `Ui` is the most important type within Conrod and is necessary for rendering and maintaining
@@ -49,6 +70,8 @@ pub struct Ui {
     pub global_input: input::Global,
     /// Manages all fonts that have been loaded by the user.
     pub fonts: text::font::Map,
+    /// The clipboard shared between all widgets for copy, cut and paste.
+    pub clipboard: Box<Clipboard>,
     /// The Widget cache, storing state for all widgets.
     widget_graph: Graph,
     /// The widget::Index of the widget that was last updated/set.
@@ -71,13 +94,96 @@ pub struct Ui {
     /// We use this to compare against the newly generated `updated_widgets` to see whether or not
     /// we require re-drawing.
     prev_updated_widgets: std::collections::HashSet<NodeIndex>,
-    /// Scroll events that have been emitted during a call to `Ui::set_widgets`. These are usually
-    /// emitted by some widget like the `Scrollbar`.
+    /// Scroll and fling events that have been emitted during a call to `Ui::set_widgets`. These
+    /// are usually emitted by some widget like the `Scrollbar`.
     ///
     /// These events will be drained and pushed onto the end of the `global_input` event buffer at
     /// the end of the `Ui::set_widgets` method. This ensures that the events are received by the
     /// target widgets during the next call to `Ui::set_widgets`.
     pending_scroll_events: Vec<event::Ui>,
+    /// The time reported by `Render` events since the last call to `Ui::set_widgets`, by which
+    /// scroll animations are stepped.
+    animation_secs: f64,
+    /// The area occupied by the text cursor of the widget capturing the keyboard, if any.
+    ///
+    /// Set by text widgets during `Ui::set_widgets` so that the backend may position the input
+    /// method's candidate window.
+    caret_rect: Option<Rect>,
+    /// The focusable widgets instantiated during the most recent call to `Ui::set_widgets`, along
+    /// with their tab indices, in the order in which they were instantiated.
+    focusable_widgets: Vec<(u32, widget::Index)>,
+    /// The widgets that use the `Tab` key themselves while capturing the keyboard.
+    ///
+    /// `Tab` presses will not move keyboard focus away from these widgets unless `Ctrl` is held.
+    tab_consuming_widgets: Vec<widget::Index>,
+    /// Whether or not the last key pressed was a `Tab` that moved keyboard focus.
+    ///
+    /// If so, the `Text` produced by the `Tab` is not delivered to the newly focused widget.
+    is_tab_text_suppressed: bool,
+    /// The keyboard shortcuts registered by the user.
+    shortcuts: input::shortcut::Registry,
+    /// The `KeyBindings` preset for the target platform.
+    ///
+    /// This is built once and shared by each editing widget that is given no other bindings.
+    platform_key_bindings: widget::text_edit::KeyBindings,
+    /// The key combinations used by the widgets that capture the keyboard.
+    ///
+    /// These key presses do not trigger shortcuts while the widget captures the keyboard.
+    key_consuming_widgets: Vec<(widget::Index, input::shortcut::Combo)>,
+    /// The widgets that use character keys to enter text while capturing the keyboard.
+    text_input_widgets: Vec<widget::Index>,
+    /// The mouse cursor icons requested by widgets during the most recent call to
+    /// `Ui::set_widgets`.
+    mouse_cursors: Vec<(widget::Index, MouseCursor)>,
+    /// The events marked as handled by the widgets that received them during the current call to
+    /// `Ui::set_widgets`.
+    ///
+    /// All other events that may bubble are passed on to the ancestors of the widget that received
+    /// them if `bubble_events` is enabled.
+    handled_events: Vec<(widget::Index, event::Widget)>,
+    /// Whether or not events left unhandled by a widget bubble up to its ancestors.
+    bubble_events: bool,
+    /// Keyboard focus that has been requested during a call to `Ui::set_widgets`.
+    ///
+    /// This is applied at the end of the `Ui::set_widgets` method so that the resulting events are
+    /// received by the widgets during the next call to `Ui::set_widgets`.
+    maybe_pending_keyboard_focus: Option<Option<widget::Index>>,
+    /// The index of the ring drawn around the focusable widget that captures the keyboard.
+    focus_ring: NodeIndex,
+    /// The widget over which the mouse is currently hovering along with the position of the mouse
+    /// at the time the `Hover` occurred.
+    ///
+    /// This is reset once the mouse leaves the widget or any mouse button is pressed.
+    maybe_hovered: Option<(widget::Index, Point)>,
+    /// The text of the tooltip given to the hovered widget during the current call to
+    /// `Ui::set_widgets`, if any.
+    ///
+    /// This is either the `'static` text given via `Widget::tooltip`, or text produced at runtime
+    /// and given via `UiCell::set_tooltip`.
+    maybe_tooltip: Option<std::borrow::Cow<'static, str>>,
+    /// The index of the **Tooltip** displayed for the hovered widget.
+    tooltip: NodeIndex,
+    /// The drag-and-drop that is currently in progress, if any.
+    maybe_drag_and_drop: Option<input::drag_and_drop::DragAndDrop>,
+    /// A drag-and-drop whose payload has been dropped upon a widget but not yet accepted.
+    ///
+    /// The target widget may accept the payload during the next call to `Ui::set_widgets`, after
+    /// which the drag-and-drop is ended.
+    maybe_dropped: Option<input::drag_and_drop::DragAndDrop>,
+    /// The `DragEnd` of a drag-and-drop whose payload was dropped, along with its source.
+    maybe_pending_drag_end: Option<(widget::Index, event::DragEnd)>,
+    /// The index of the preview displayed near the mouse during a drag-and-drop.
+    drag_preview: NodeIndex,
+    /// Whether or not the primary finger touching the screen drives the mouse.
+    touch_emulates_mouse: bool,
+    /// The finger that is currently driving the mouse, if any.
+    maybe_mouse_touch: Option<input::state::touch::Id>,
+    /// The mapping of controller buttons and axes to navigation between widgets.
+    controller_bindings: input::controller::Bindings,
+    /// The time used in place of `Instant::now` while replaying a `record::Recording`.
+    maybe_clock: Option<std::time::Instant>,
+    /// Records each event given to `Ui::handle_event` while recording.
+    maybe_recorder: Option<record::Recorder>,
 
     // TODO: Remove the following fields as they should now be handled by `input::Global`.
 
@@ -108,13 +214,21 @@ pub struct UiCell<'a> {
 /// buffer. Otherwise if we don't draw into each buffer, we will probably be subject to flickering.
 pub const SAFE_REDRAW_COUNT: u8 = 3;
 
+/// A panning finger that rests for longer than this many milliseconds before it is lifted comes
+/// to rest rather than flinging the content beneath it.
+const MAX_FLING_REST_MS: u64 = 100;
+
 impl UiBuilder {
 
     /// Begin building a new `Ui` instance.
     pub fn new() -> Self {
         UiBuilder {
             maybe_theme: None,
-            maybe_widgets_capacity: None
+            maybe_widgets_capacity: None,
+            maybe_clipboard: None,
+            touch_emulates_mouse: false,
+            controller_bindings: input::controller::Bindings::default(),
+            bubble_events: false,
         }
     }
 
@@ -144,6 +258,54 @@ impl UiBuilder {
         self
     }
 
+    /// The clipboard used by text widgets for copy, cut and paste.
+    ///
+    /// If this field is `None` when `build` is called, an in-memory `clipboard::Memory` will be
+    /// used.
+    pub fn clipboard<C>(mut self, value: C) -> Self
+        where C: Clipboard + 'static,
+    {
+        self.maybe_clipboard = Some(Box::new(value));
+        self
+    }
+
+    /// Whether or not the primary finger touching the screen should drive the mouse.
+    ///
+    /// The primary finger is the first to touch the screen while no other fingers are touching
+    /// it. When enabled, this finger moves the mouse cursor and presses and releases the left mouse
+    /// button, so that widgets which only handle the mouse produce clicks and drags from touch.
+    ///
+    /// `Touch` events and gestures are produced regardless. The default is `false`.
+    pub fn touch_emulates_mouse(mut self, value: bool) -> Self {
+        self.touch_emulates_mouse = value;
+        self
+    }
+
+    /// The mapping of controller buttons and axes to navigation between widgets.
+    ///
+    /// Controller buttons may move keyboard focus between focusable widgets, and activate or
+    /// cancel as though `Return` or `Escape` were pressed. Controller sticks may move focus or
+    /// adjust the value of the focused widget. See `input::controller::Bindings` for details.
+    pub fn controller_bindings(mut self, value: input::controller::Bindings) -> Self {
+        self.controller_bindings = value;
+        self
+    }
+
+    /// Whether or not events left unhandled by a widget bubble up to its ancestors.
+    ///
+    /// When enabled, each `Text`, `Press`, `Release`, `Click`, `DoubleClick`, `MultiClick`,
+    /// `Drag`, `Scroll`, `Tap` and `LongPress` event that the widget receiving it does not mark as
+    /// handled via `UiCell::set_event_handled` is passed on along its `Edge::Depth` parents. Each
+    /// ancestor receives it as an `event::Widget::Bubble` within the same call to
+    /// `Ui::set_widgets`, until one of them marks the `Bubble` as handled.
+    ///
+    /// This allows a panel to respond to keyboard shortcuts while one of its descendants has
+    /// keyboard focus. The default is `false`.
+    pub fn bubble_events(mut self, value: bool) -> Self {
+        self.bubble_events = value;
+        self
+    }
+
     /// Build **Ui** from the given builder
     pub fn build(self) -> Ui {
         Ui::new(self)
@@ -165,11 +327,16 @@ impl Ui {
                      std::collections::HashSet::with_capacity(n)));
 
         let window = widget_graph.add_placeholder();
+        let focus_ring = widget_graph.add_placeholder();
+        let tooltip = widget_graph.add_placeholder();
+        let drag_preview = widget_graph.add_placeholder();
         let prev_updated_widgets = updated_widgets.clone();
         Ui {
             widget_graph: widget_graph,
             theme: builder.maybe_theme.unwrap_or_else(|| Theme::default()),
             fonts: text::font::Map::new(),
+            clipboard: builder.maybe_clipboard
+                .unwrap_or_else(|| Box::new(clipboard::Memory::new())),
             window: window,
             win_w: 0.0,
             win_h: 0.0,
@@ -183,9 +350,67 @@ impl Ui {
             prev_updated_widgets: prev_updated_widgets,
             global_input: input::Global::new(),
             pending_scroll_events: Vec::new(),
+            animation_secs: 0.0,
+            caret_rect: None,
+            focusable_widgets: Vec::new(),
+            tab_consuming_widgets: Vec::new(),
+            is_tab_text_suppressed: false,
+            shortcuts: input::shortcut::Registry::new(),
+            platform_key_bindings: widget::text_edit::KeyBindings::default(),
+            key_consuming_widgets: Vec::new(),
+            text_input_widgets: Vec::new(),
+            mouse_cursors: Vec::new(),
+            handled_events: Vec::new(),
+            maybe_pending_keyboard_focus: None,
+            focus_ring: focus_ring,
+            maybe_hovered: None,
+            maybe_tooltip: None,
+            tooltip: tooltip,
+            maybe_drag_and_drop: None,
+            maybe_dropped: None,
+            maybe_pending_drag_end: None,
+            drag_preview: drag_preview,
+            touch_emulates_mouse: builder.touch_emulates_mouse,
+            maybe_mouse_touch: None,
+            controller_bindings: builder.controller_bindings,
+            bubble_events: builder.bubble_events,
+            maybe_clock: None,
+            maybe_recorder: None,
         }
     }
 
+    /// The current time as seen by the `Ui` when interpreting events, i.e. `DoubleClick`s.
+    ///
+    /// This is `Instant::now` unless some other time has been set via `Ui::set_clock`.
+    pub fn now(&self) -> std::time::Instant {
+        self.maybe_clock.unwrap_or_else(std::time::Instant::now)
+    }
+
+    /// Set the time used by the `Ui` in place of `Instant::now`, or `None` to use the real time.
+    ///
+    /// This is used by the `record::Player` so that replayed events are interpreted exactly as
+    /// they were when recorded.
+    pub fn set_clock(&mut self, maybe_now: Option<std::time::Instant>) {
+        self.maybe_clock = maybe_now;
+    }
+
+    /// Begin recording each event given to `Ui::handle_event` and `Ui::handle_composition`.
+    ///
+    /// Any recording already in progress is discarded.
+    pub fn start_recording(&mut self) {
+        self.maybe_recorder = Some(record::Recorder::new());
+    }
+
+    /// Stop recording, returning the events recorded since `Ui::start_recording` was called.
+    pub fn stop_recording(&mut self) -> Option<record::Recording> {
+        self.maybe_recorder.take().map(|recorder| recorder.into_recording())
+    }
+
+    /// Whether or not the events given to `Ui::handle_event` are being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.maybe_recorder.is_some()
+    }
+
     /// Returns a `input::Widget` for the given widget
     pub fn widget_input<I: Into<widget::Index>>(&self, widget: I) -> input::Widget {
         let idx = widget.into();
@@ -198,7 +423,15 @@ impl Ui {
             let bottom_edge = self.win_h / 2.0;
             Rect::from_xy_dim([right_edge, bottom_edge], [0.0, 0.0])
         });
-        input::Widget::for_widget(idx, rect, &self.global_input)
+        let input = input::Widget::for_widget(idx, rect, &self.global_input);
+        if !self.bubble_events {
+            return input;
+        }
+        input.with_bubbling(input::widget::Bubbling {
+            graph: &self.widget_graph,
+            updated_widgets: &self.updated_widgets,
+            handled_events: &self.handled_events,
+        })
     }
 
     /// The **Rect** for the widget at the given index.
@@ -292,6 +525,21 @@ impl Ui {
         }
     }
 
+    /// Give the scrolling of the widget at the given index the given velocity in points per
+    /// second, along each axis.
+    ///
+    /// The fling carries the widget's scroll offset onwards until its momentum is lost to the
+    /// `theme.scroll_animation`'s friction.
+    pub fn fling_widget<I>(&mut self, widget_idx: I, velocity: [Scalar; 2])
+        where I: Into<widget::Index>,
+    {
+        if velocity[0] != 0.0 || velocity[1] != 0.0 {
+            let fling = event::Fling { velocity: velocity };
+            let event = event::Ui::Fling(Some(widget_idx.into()), fling).into();
+            self.global_input.push_event(event);
+        }
+    }
+
     /// Handle raw window events and update the `Ui` state accordingly.
     ///
     /// This occurs within several stages:
@@ -309,6 +557,18 @@ impl Ui {
     /// The given `event` must implement the **ToRawEvent** trait so that it can be converted to a
     /// `RawEvent` that can be used by the `Ui`.
     pub fn handle_event<E: Into<event::Raw>>(&mut self, event: E) {
+        let event: event::Raw = event.into();
+        if let Some(ref mut recorder) = self.maybe_recorder {
+            recorder.record(event.clone());
+        }
+        self.handle_raw_event(event);
+    }
+
+    // Interpret the given event as described by `Ui::handle_event`.
+    //
+    // Events produced by the `Ui` itself (i.e. while emulating the mouse with touch) are handled
+    // here directly, so that they are not recorded alongside the events from which they came.
+    fn handle_raw_event(&mut self, event: event::Raw) {
         use event::{Input, Motion};
         use input::{Key, ModifierKey};
 
@@ -327,11 +587,27 @@ impl Ui {
         //
         // Note: This function expects that `ui.global_input.current.mouse.xy` is up-to-date.
         fn track_widget_under_mouse_and_update_capturing(ui: &mut Ui) {
+            let prev_widget_under_mouse = ui.global_input.current.widget_under_mouse;
             ui.global_input.current.widget_under_mouse =
                 graph::algo::pick_widgets(&ui.depth_order.indices,
                                           ui.global_input.current.mouse.xy)
                                           .next(&ui.widget_graph, &ui.depth_order.indices);
 
+            // If the `widget_under_mouse` has changed, the mouse has left the previous widget and
+            // entered the new one, and must rest over the new one before hovering.
+            let widget_under_mouse = ui.global_input.current.widget_under_mouse;
+            if widget_under_mouse != prev_widget_under_mouse {
+                if let Some(idx) = prev_widget_under_mouse {
+                    ui.global_input.push_event(event::Ui::MouseLeave(idx).into());
+                }
+                if let Some(idx) = widget_under_mouse {
+                    ui.global_input.push_event(event::Ui::MouseEnter(idx).into());
+                }
+                ui.global_input.mouse_rest_secs = 0.0;
+                ui.global_input.has_hovered = false;
+                ui.maybe_hovered = None;
+            }
+
             // If MouseButton::Left is up and `widget_under_mouse` has changed, capture new widget
             // under mouse.
             if ui.global_input.current.mouse.buttons.left().is_up() {
@@ -369,9 +645,6 @@ impl Ui {
             }
         }
 
-        // Convert the user given event to a `RawEvent` or return early if we cannot.
-        let event: event::Raw = event.into();
-
         match event {
 
             // On each `Render` we should check that our window dimensions are up to date.
@@ -385,6 +658,34 @@ impl Ui {
                     self.win_h = h;
                     track_widget_under_mouse_and_update_capturing(self);
                 }
+
+                // Step scroll animations by the time elapsed during the next `Ui::set_widgets`.
+                self.animation_secs += args.ext_dt;
+
+                // Produce a `Hover` once the mouse has rested over a widget for the `hover_delay`.
+                self.global_input.mouse_rest_secs += args.ext_dt;
+                if !self.global_input.has_hovered {
+                    let delay = self.theme.hover_delay;
+                    let delay_secs = delay.as_secs() as f64 + delay.subsec_nanos() as f64 * 1e-9;
+                    if self.global_input.mouse_rest_secs >= delay_secs {
+                        if let Some(idx) = self.global_input.current.widget_under_mouse {
+                            let mouse_xy = self.global_input.current.mouse.xy;
+                            let hover = event::Hover {
+                                xy: mouse_xy,
+                                modifiers: self.global_input.current.modifiers,
+                            };
+                            self.global_input.push_event(event::Ui::Hover(Some(idx), hover).into());
+                            self.global_input.has_hovered = true;
+                            self.maybe_hovered = Some((idx, mouse_xy));
+                        }
+                    }
+                }
+
+                // Adjust the focused widget with any controller sticks pushed beyond the dead zone.
+                if let Some(adjust) = self.controller_adjustment(args.ext_dt) {
+                    let widget = self.global_input.current.widget_capturing_keyboard;
+                    self.global_input.push_event(event::Ui::Adjust(widget, adjust).into());
+                }
             },
 
             // Here we handle all user input given to conrod.
@@ -419,6 +720,9 @@ impl Ui {
                             let press_event = event::Ui::Press(widget, press).into();
                             self.global_input.push_event(press_event);
 
+                            // Pressing any mouse button hides the tooltip until the mouse moves.
+                            self.maybe_hovered = None;
+
                             if let MouseButton::Left = mouse_button {
                                 // Check to see if we need to uncapture the keyboard.
                                 if let Some(idx) = self.global_input.current.widget_capturing_keyboard {
@@ -459,17 +763,63 @@ impl Ui {
                                 self.global_input.current.modifiers.insert(modifier);
                             }
 
+                            // If `Tab` was pressed, move keyboard focus to the next focusable widget
+                            // (or the previous one if `Shift` is held), unless the widget capturing
+                            // the keyboard uses `Tab` itself and `Ctrl` is not held.
+                            self.is_tab_text_suppressed = false;
+                            if let Key::Tab = key {
+                                use input::keyboard::{ALT, CTRL, GUI, SHIFT};
+                                let modifiers = self.global_input.current.modifiers;
+                                let is_consumed = self.global_input.current.widget_capturing_keyboard
+                                    .map_or(false, |idx| self.tab_consuming_widgets.contains(&idx));
+                                if !modifiers.intersects(ALT | GUI)
+                                && (!is_consumed || modifiers.contains(CTRL)) {
+                                    // Only drop the tab entered by this key press if it moved
+                                    // focus.
+                                    let forward = !modifiers.contains(SHIFT);
+                                    self.is_tab_text_suppressed = self.step_focus(forward);
+                                }
+                            }
+
+                            // Check whether the key press completes a registered shortcut, unless
+                            // the widget capturing the keyboard uses the key press itself.
+                            if filter_modifier(key).is_none() {
+                                let modifiers = self.global_input.current.modifiers;
+                                let combo = input::shortcut::Combo::new(modifiers, key);
+                                let widget = self.global_input.current.widget_capturing_keyboard;
+                                let is_consumed = widget.map_or(false, |idx| {
+                                    self.key_consuming_widgets.contains(&(idx, combo))
+                                    || (combo.enters_text() && self.text_input_widgets.contains(&idx))
+                                });
+                                if is_consumed {
+                                    self.shortcuts.reset();
+                                } else if let Some(id) = self.shortcuts.press(combo) {
+                                    self.global_input.push_event(event::Ui::Shortcut(id).into());
+                                }
+                            }
+
                             // If `Esc` was pressed, check to see if we need to cancel a `Drag` or
                             // uncapture a widget.
                             if let Key::Escape = key {
-                                // TODO:
-                                // 1. Cancel `Drag` if currently under way.
-                                // 2. If mouse is captured due to pinning widget with left mouse button,
-                                //    cancel capturing.
+                                if self.cancel_drag() {
+                                    track_widget_under_mouse_and_update_capturing(self);
+                                }
                             }
                         },
 
-                        _ => {}
+                        Button::Controller(button) => {
+
+                            // Create a controller `Press` event.
+                            let press = event::Press {
+                                button: event::Button::Controller(button),
+                                modifiers: self.global_input.current.modifiers,
+                            };
+                            let widget = self.global_input.current.widget_capturing_keyboard;
+                            let press_event = event::Ui::Press(widget, press).into();
+                            self.global_input.push_event(press_event);
+
+                            self.handle_controller_button(button, true);
+                        },
                     },
 
                     // Some button was released.
@@ -477,7 +827,8 @@ impl Ui {
                     // Checks for events in the following order:
                     // 1. Click
                     // 2. DoubleClick
-                    // 2. WidgetUncapturesMouse
+                    // 3. MultiClick
+                    // 4. WidgetUncapturesMouse
                     Input::Release(button_type) => match button_type {
                         Button::Mouse(mouse_button) => {
 
@@ -510,56 +861,69 @@ impl Ui {
                                 let click_event = event::Ui::Click(clicked_widget, click).into();
                                 self.global_input.push_event(click_event);
 
-                                let now = std::time::Instant::now();
-                                let double_click = self.global_input.last_click
-                                    .and_then(|(last_time, last_click)| {
+                                let now = self.now();
+                                let is_consecutive = self.global_input.last_click
+                                    .map_or(false, |(last_time, last_click)| {
 
                                         // If the button of this click is different to the button
-                                        // of last click, don't create a `DoubleClick`.
+                                        // of last click, the clicks are not consecutive.
                                         if click.button != last_click.button {
-                                            return None;
+                                            return false;
                                         }
 
-                                        // If the mouse has moved since the last click, don't
-                                        // create a `DoubleClick`.
+                                        // If the mouse has moved since the last click, the clicks
+                                        // are not consecutive.
                                         if click.xy != last_click.xy {
-                                            return None;
+                                            return false;
                                         }
 
                                         // If the duration since the last click is longer than the
-                                        // double_click_threshold, don't create a `DoubleClick`.
+                                        // double_click_threshold, the clicks are not consecutive.
                                         let duration = now.duration_since(last_time);
                                         // TODO: Work out how to get this threshold from the user's
                                         // system preferences.
                                         let threshold = self.theme.double_click_threshold;
-                                        if duration >= threshold {
-                                            return None;
-                                        }
-
-                                        Some(event::DoubleClick {
-                                            button: click.button,
-                                            xy: click.xy,
-                                            modifiers: click.modifiers,
-                                        })
+                                        duration < threshold
                                     });
 
-                                if let Some(double_click) = double_click {
-                                    // Reset the `last_click` to `None`, as to not register another
-                                    // `DoubleClick` on the next consecutive `Click`.
-                                    self.global_input.last_click = None;
+                                // Set the `Click` that we just stored as the `last_click` so that
+                                // each following click may be counted.
+                                let count = if is_consecutive {
+                                    self.global_input.click_count + 1
+                                } else {
+                                    1
+                                };
+                                self.global_input.last_click = Some((now, click));
+                                self.global_input.click_count = count;
+
+                                if count == 2 {
+                                    let double_click = event::DoubleClick {
+                                        button: click.button,
+                                        xy: click.xy,
+                                        modifiers: click.modifiers,
+                                    };
                                     let double_click_event =
                                         event::Ui::DoubleClick(clicked_widget, double_click).into();
                                     self.global_input.push_event(double_click_event);
+                                }
 
-                                } else {
-                                    // Set the `Click` that we just stored as the `last_click`.
-                                    self.global_input.last_click = Some((now, click));
+                                if count >= 2 {
+                                    let multi_click = event::MultiClick {
+                                        button: click.button,
+                                        xy: click.xy,
+                                        modifiers: click.modifiers,
+                                        count: count,
+                                    };
+                                    let multi_click_event =
+                                        event::Ui::MultiClick(clicked_widget, multi_click).into();
+                                    self.global_input.push_event(multi_click_event);
                                 }
                             }
 
                             // Uncapture widget capturing mouse if MouseButton::Left is down and
                             // widget_under_mouse != capturing widget.
                             if let MouseButton::Left = mouse_button {
+                                self.drop_payload_on_widget_under_mouse();
                                 if let Some(idx) = self.global_input.current.widget_capturing_mouse {
                                     if Some(idx) != self.global_input.current.widget_under_mouse {
                                         let event = event::Ui::WidgetUncapturesMouse(idx).into();
@@ -590,7 +954,19 @@ impl Ui {
                             }
                         },
 
-                        _ => (),
+                        Button::Controller(button) => {
+
+                            // Create a controller `Release` event.
+                            let release = event::Release {
+                                button: event::Button::Controller(button),
+                                modifiers: self.global_input.current.modifiers,
+                            };
+                            let widget = self.global_input.current.widget_capturing_keyboard;
+                            let release_event = event::Ui::Release(widget, release).into();
+                            self.global_input.push_event(release_event);
+
+                            self.handle_controller_button(button, false);
+                        },
                     },
 
                     // The window was resized.
@@ -654,7 +1030,14 @@ impl Ui {
                                 // input::State.
                                 self.global_input.current.mouse.xy = mouse_xy;
 
+                                // The mouse must rest in its new position before hovering again.
+                                if mouse_xy != last_mouse_xy {
+                                    self.global_input.mouse_rest_secs = 0.0;
+                                    self.global_input.has_hovered = false;
+                                }
+
                                 track_widget_under_mouse_and_update_capturing(self);
+                                self.drag_payload_over_widget_under_mouse();
                             },
 
                             // The mouse was scrolled.
@@ -698,11 +1081,11 @@ impl Ui {
                                         if additional_offset.is_sign_positive() {
                                             let max = utils::partial_max(scroll.offset_bounds.start,
                                                                          scroll.offset_bounds.end);
-                                            approx_eq(scroll.offset, max)
+                                            approx_eq(scroll.target_offset, max)
                                         } else {
                                             let min = utils::partial_min(scroll.offset_bounds.start,
                                                                          scroll.offset_bounds.end);
-                                            approx_eq(scroll.offset, min)
+                                            approx_eq(scroll.target_offset, min)
                                         }
                                     }
 
@@ -778,12 +1161,30 @@ impl Ui {
                                 track_widget_under_mouse_and_update_capturing(self);
                             },
 
+                            // A finger touched, moved across or was lifted from the screen.
+                            Motion::Touch(args) => {
+                                self.handle_touch(args);
+                                if self.touch_emulates_mouse {
+                                    self.emulate_mouse_with_touch(args);
+                                }
+                            },
+
+                            // A controller stick or trigger moved.
+                            Motion::ControllerAxis(args) => self.handle_controller_axis(args),
+
                             _ => (),
 
                         }
                     },
 
                     Input::Text(string) => {
+                        // Drop the tab entered by a `Tab` press that moved keyboard focus.
+                        let is_tab_text_suppressed = self.is_tab_text_suppressed;
+                        self.is_tab_text_suppressed = false;
+                        if is_tab_text_suppressed && string == "\t" {
+                            return;
+                        }
+
                         // Create a `Text` event.
                         let text = event::Text {
                             string: string,
@@ -803,6 +1204,362 @@ impl Ui {
         }
     }
 
+    // Track the state of each touching finger, producing `Touch` events and recognising gestures.
+    //
+    // All events produced by a finger are delivered to the widget that was under the finger when
+    // it first touched the screen.
+    fn handle_touch(&mut self, args: input::TouchArgs) {
+        use input::state::touch;
+
+        fn magnitude(v: Point) -> Scalar {
+            (v[0] * v[0] + v[1] * v[1]).sqrt()
+        }
+
+        let id = touch::Id(args.id);
+        let xy = [args.x, args.y];
+        let now = self.now();
+        let touch_event = |widget, phase| -> event::Event {
+            event::Ui::Touch(widget, event::Touch { phase: phase, id: id, xy: xy }).into()
+        };
+
+        match args.touch {
+
+            input::Touch::Start => {
+                let widget = graph::algo::pick_widgets(&self.depth_order.indices, xy)
+                    .next(&self.widget_graph, &self.depth_order.indices);
+                self.global_input.touch.insert(id, touch::Touch {
+                    start_time: now,
+                    start_xy: xy,
+                    widget: widget,
+                    xy: xy,
+                    has_moved: false,
+                    is_long_press: false,
+                    move_time: now,
+                    velocity: [0.0, 0.0],
+                    panned_widget: None,
+                });
+                self.global_input.push_event(touch_event(widget, input::Touch::Start));
+            },
+
+            input::Touch::Move => {
+                let threshold = self.theme.touch_drag_threshold;
+                let (widget, from, origin, has_moved) = match self.global_input.touch.get_mut(&id) {
+                    Some(touch) => {
+                        let from = touch.xy;
+                        let distance = magnitude(utils::vec2_sub(xy, touch.start_xy));
+                        touch.has_moved = touch.has_moved || distance > threshold;
+                        touch.xy = xy;
+
+                        // Smooth the velocity over the most recent movements.
+                        let elapsed = now.duration_since(touch.move_time);
+                        let secs = elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 * 1e-9;
+                        if secs > 0.0 {
+                            let delta = utils::vec2_sub(xy, from);
+                            touch.velocity = [(touch.velocity[0] + delta[0] / secs) / 2.0,
+                                              (touch.velocity[1] + delta[1] / secs) / 2.0];
+                            touch.move_time = now;
+                        }
+
+                        (touch.widget, from, touch.start_xy, touch.has_moved)
+                    },
+                    None => return,
+                };
+                self.global_input.push_event(touch_event(widget, input::Touch::Move));
+
+                // The positions of the other fingers touching the same widget.
+                let others: Vec<Point> = self.global_input.touch.iter()
+                    .filter(|&(&other_id, other)| other_id != id && other.widget == widget)
+                    .map(|(_, other)| other.xy)
+                    .collect();
+
+                match others.len() {
+
+                    // A single finger pans once it has moved beyond the threshold.
+                    0 => if has_moved && from != xy {
+                        let pan = event::Pan {
+                            id: id,
+                            origin: origin,
+                            from: from,
+                            to: xy,
+                            delta_xy: utils::vec2_sub(xy, from),
+                            total_delta_xy: utils::vec2_sub(xy, origin),
+                        };
+                        self.global_input.push_event(event::Ui::Pan(widget, pan).into());
+
+                        // Panning drags the content of the scrollable widget under the finger.
+                        if let Some(scrollable) = self.scrollable_widget_at(origin) {
+                            if let Some(touch) = self.global_input.touch.get_mut(&id) {
+                                touch.panned_widget = Some(scrollable);
+                            }
+                            let scroll = event::Scroll {
+                                x: pan.delta_xy[0],
+                                y: pan.delta_xy[1],
+                                modifiers: self.global_input.current.modifiers,
+                            };
+                            let event = event::Ui::Scroll(Some(scrollable), scroll);
+                            self.global_input.push_event(event.into());
+                        }
+                    },
+
+                    // Two fingers pinch and rotate around the point between them.
+                    1 => {
+                        let other = others[0];
+                        let prev = utils::vec2_sub(from, other);
+                        let next = utils::vec2_sub(xy, other);
+                        let (prev_distance, next_distance) = (magnitude(prev), magnitude(next));
+                        if prev_distance == 0.0 || next_distance == 0.0 {
+                            return;
+                        }
+                        let centre = [(xy[0] + other[0]) / 2.0, (xy[1] + other[1]) / 2.0];
+
+                        if next_distance != prev_distance {
+                            let pinch = event::Pinch {
+                                centre: centre,
+                                scale: next_distance / prev_distance,
+                            };
+                            self.global_input.push_event(event::Ui::Pinch(widget, pinch).into());
+                        }
+
+                        // Wrap the change in angle to the range -PI...PI.
+                        let pi = std::f64::consts::PI;
+                        let mut radians = next[1].atan2(next[0]) - prev[1].atan2(prev[0]);
+                        if radians > pi {
+                            radians -= 2.0 * pi;
+                        } else if radians < -pi {
+                            radians += 2.0 * pi;
+                        }
+                        if radians != 0.0 {
+                            let rotate = event::Rotate {
+                                centre: centre,
+                                radians: radians,
+                            };
+                            self.global_input.push_event(event::Ui::Rotate(widget, rotate).into());
+                        }
+                    },
+
+                    _ => (),
+                }
+            },
+
+            input::Touch::End | input::Touch::Cancel => {
+                let touch = match self.global_input.touch.remove(&id) {
+                    Some(touch) => touch,
+                    None => return,
+                };
+                self.global_input.push_event(touch_event(touch.widget, args.touch));
+
+                // A finger that is lifted before moving or long pressing has tapped.
+                if let input::Touch::End = args.touch {
+                    let duration = now.duration_since(touch.start_time);
+                    if !touch.has_moved && !touch.is_long_press
+                    && duration < self.theme.long_press_duration {
+                        let tap = event::Tap { id: id, xy: xy };
+                        self.global_input.push_event(event::Ui::Tap(touch.widget, tap).into());
+                    }
+
+                    // A finger lifted while panning flings the scrollable widget under it onwards.
+                    let max_rest = std::time::Duration::from_millis(MAX_FLING_REST_MS);
+                    if touch.has_moved && now.duration_since(touch.move_time) < max_rest {
+                        if let Some(scrollable) = self.scrollable_widget_at(touch.start_xy) {
+                            self.fling_widget(scrollable, touch.velocity);
+                        }
+                    }
+                }
+            },
+        }
+    }
+
+    // The top-most scrollable widget at the given position, if any.
+    fn scrollable_widget_at(&self, xy: Point) -> Option<widget::Index> {
+        graph::algo::pick_scrollable_widgets(&self.depth_order.indices, xy)
+            .next(&self.widget_graph, &self.depth_order.indices)
+    }
+
+    // Drive the mouse with the primary finger, being the first finger to touch the screen while no
+    // other fingers were touching it.
+    fn emulate_mouse_with_touch(&mut self, args: input::TouchArgs) {
+        use event::{Input, Motion};
+        use input::Button;
+        use input::state::mouse::Button as MouseButton;
+
+        let id = input::state::touch::Id(args.id);
+        match args.touch {
+            input::Touch::Start => {
+                // `handle_touch` has already inserted this finger, so any other is already down.
+                if self.maybe_mouse_touch.is_some() || self.global_input.touch.len() > 1 {
+                    return;
+                }
+                self.maybe_mouse_touch = Some(id);
+                self.handle_raw_event(Input::Move(Motion::MouseCursor(args.x, args.y)).into());
+                self.handle_raw_event(Input::Press(Button::Mouse(MouseButton::Left)).into());
+            },
+            input::Touch::Move => if self.maybe_mouse_touch == Some(id) {
+                self.handle_raw_event(Input::Move(Motion::MouseCursor(args.x, args.y)).into());
+            },
+            input::Touch::End | input::Touch::Cancel => if self.maybe_mouse_touch == Some(id) {
+                self.maybe_mouse_touch = None;
+                self.handle_raw_event(Input::Release(Button::Mouse(MouseButton::Left)).into());
+            },
+        }
+    }
+
+    // Perform the `controller::Action` bound to the given button, if any.
+    //
+    // `Activate` and `Cancel` are performed by pressing and releasing `Return` and `Escape`, so
+    // that widgets which only handle the keyboard may be used with a controller.
+    fn handle_controller_button(&mut self, button: input::ControllerButton, is_press: bool) {
+        use event::Input;
+        use input::{Button, Key};
+        use input::controller::Action;
+
+        let key = match self.controller_bindings.action(button.button) {
+            Some(Action::Navigate(axis, direction)) => {
+                if is_press {
+                    self.focus_towards(axis, direction);
+                }
+                return;
+            },
+            Some(Action::Activate) => Key::Return,
+            Some(Action::Cancel) => Key::Escape,
+            None => return,
+        };
+        if is_press {
+            self.handle_raw_event(Input::Press(Button::Keyboard(key)).into());
+        } else {
+            self.handle_raw_event(Input::Release(Button::Keyboard(key)).into());
+        }
+    }
+
+    // Track the position of the given controller axis, moving keyboard focus each time one of the
+    // `navigation_axes` is pushed out of the dead zone.
+    fn handle_controller_axis(&mut self, args: input::ControllerAxisArgs) {
+        let prev_position = self.global_input.controller_axes
+            .insert((args.id, args.axis), args.position)
+            .unwrap_or(0.0);
+
+        let axes = match self.controller_bindings.navigation_axes {
+            Some(axes) => axes,
+            None => return,
+        };
+        let axis = if args.axis == axes[0] {
+            Axis::X
+        } else if args.axis == axes[1] {
+            Axis::Y
+        } else {
+            return;
+        };
+
+        // The direction in which the stick is pushed, where `0` is within the dead zone.
+        let dead_zone = self.controller_bindings.dead_zone;
+        let pushed = |position: f64| if position > dead_zone {
+            1
+        } else if position < -dead_zone {
+            -1
+        } else {
+            0
+        };
+        let (prev_pushed, pushed) = (pushed(prev_position), pushed(args.position));
+        if pushed == 0 || pushed == prev_pushed {
+            return;
+        }
+
+        // Controller sticks point downwards along the positive vertical axis.
+        let is_forwards = match axis {
+            Axis::X => pushed > 0,
+            Axis::Y => pushed < 0,
+        };
+        let direction = if is_forwards { Direction::Forwards } else { Direction::Backwards };
+        self.focus_towards(axis, direction);
+    }
+
+    // The adjustment requested by the `adjustment_axes` of all controllers over the given
+    // duration, or `None` if every stick is within the dead zone.
+    fn controller_adjustment(&self, secs: f64) -> Option<event::Adjust> {
+        let axes = match self.controller_bindings.adjustment_axes {
+            Some(axes) => axes,
+            None => return None,
+        };
+        let mut amount = [0.0, 0.0];
+        for (&(_, axis), &position) in self.global_input.controller_axes.iter() {
+            let position = self.controller_bindings.filter_dead_zone(position);
+            if axis == axes[0] {
+                amount[0] += position * secs;
+            } else if axis == axes[1] {
+                // Controller sticks point downwards along the positive vertical axis.
+                amount[1] -= position * secs;
+            }
+        }
+        if amount == [0.0, 0.0] {
+            None
+        } else {
+            Some(event::Adjust { amount: amount })
+        }
+    }
+
+    /// Handle an input method (IME) composition event.
+    ///
+    /// The event is delivered to the widget capturing the keyboard as an
+    /// `event::Ui::Composition`. A `Commit` is also delivered as an `event::Ui::Text`, so that
+    /// widgets that do not handle composition still receive the committed text.
+    pub fn handle_composition(&mut self, composition: event::Composition) {
+        if let Some(ref mut recorder) = self.maybe_recorder {
+            recorder.record(composition.clone());
+        }
+        let widget = self.global_input.current.widget_capturing_keyboard;
+        let maybe_text_event = match composition {
+            event::Composition::Commit(ref string) if !string.is_empty() => {
+                let text = event::Text {
+                    string: string.clone(),
+                    modifiers: self.global_input.current.modifiers,
+                };
+                Some(event::Ui::Text(widget, text).into())
+            },
+            _ => None,
+        };
+        let composition_event = event::Ui::Composition(widget, composition).into();
+        self.global_input.push_event(composition_event);
+        if let Some(text_event) = maybe_text_event {
+            self.global_input.push_event(text_event);
+        }
+    }
+
+    /// The area occupied by the text cursor of the widget capturing the keyboard, as reported by
+    /// that widget during the last call to `Ui::set_widgets`.
+    ///
+    /// Backends may use this to position the input method's candidate window. Like all `Rect`s
+    /// within the `Ui`, the origin is the centre of the window with the *y* axis pointing upwards.
+    ///
+    /// Returns `None` if no widget reported a caret.
+    pub fn caret_rect(&self) -> Option<Rect> {
+        self.caret_rect
+    }
+
+    /// The `KeyBindings` used by editing widgets that are not given their own.
+    ///
+    /// These are the `Theme`'s `key_bindings` or, if it has none, the preset for the target
+    /// platform (see `KeyBindings::default`).
+    pub fn key_bindings(&self) -> &widget::text_edit::KeyBindings {
+        self.theme.key_bindings.as_ref().unwrap_or(&self.platform_key_bindings)
+    }
+
+    /// The mouse cursor icon that should currently be displayed, as requested by the widget
+    /// capturing the mouse or, if there is none, the widget under the mouse during the last call
+    /// to `Ui::set_widgets`.
+    ///
+    /// Backends may apply this to the window after each call to `Ui::set_widgets`, i.e. via
+    /// `backend::glutin::set_mouse_cursor`. Returns `MouseCursor::Arrow` if no icon was requested.
+    pub fn mouse_cursor(&self) -> MouseCursor {
+        let input = &self.global_input.current;
+        input.widget_capturing_mouse.or(input.widget_under_mouse)
+            .and_then(|idx| {
+                self.mouse_cursors.iter()
+                    .rev()
+                    .find(|&&(requested_by, _)| requested_by == idx)
+                    .map(|&(_, cursor)| cursor)
+            })
+            .unwrap_or(MouseCursor::Arrow)
+    }
+
     /// Get the centred xy coords for some given `Dimension`s, `Position` and alignment.
     ///
     /// If getting the xy for a specific widget, its `widget::Index` should be specified so that we
@@ -915,11 +1672,310 @@ impl Ui {
     }
 
 
+    /// Give keyboard capture to the widget at the given index, or to no widget if `None` is given.
+    ///
+    /// `WidgetUncapturesKeyboard` and `WidgetCapturesKeyboard` events are produced accordingly
+    /// and will be received by the widgets upon the next call to `Ui::set_widgets`.
+    pub fn set_keyboard_focus(&mut self, maybe_idx: Option<widget::Index>) {
+        let maybe_capturing = self.global_input.current.widget_capturing_keyboard;
+        if maybe_capturing == maybe_idx {
+            return;
+        }
+        if let Some(idx) = maybe_capturing {
+            let event = event::Ui::WidgetUncapturesKeyboard(idx).into();
+            self.global_input.push_event(event);
+        }
+        if let Some(idx) = maybe_idx {
+            let event = event::Ui::WidgetCapturesKeyboard(idx).into();
+            self.global_input.push_event(event);
+        }
+        self.global_input.current.widget_capturing_keyboard = maybe_idx;
+    }
+
+    /// Move keyboard focus to the next focusable widget.
+    ///
+    /// If no focusable widget currently has focus, the first focusable widget is focused. Focus
+    /// wraps around to the first widget after the last. See `Widget::tab_index` for details.
+    pub fn focus_next(&mut self) {
+        self.step_focus(true);
+    }
+
+    /// Move keyboard focus to the previous focusable widget.
+    ///
+    /// If no focusable widget currently has focus, the last focusable widget is focused. Focus
+    /// wraps around to the last widget before the first. See `Widget::tab_index` for details.
+    pub fn focus_previous(&mut self) {
+        self.step_focus(false);
+    }
+
+    /// Move keyboard focus to the nearest focusable widget in the given direction along the given
+    /// axis from the currently focused widget.
+    ///
+    /// Only widgets whose centre lies beyond the centre of the focused widget in the given
+    /// direction are considered. Distance across the axis counts double, so that widgets lined up
+    /// with the focused widget are preferred. If no focusable widget currently has focus, the first
+    /// focusable widget is focused.
+    pub fn focus_towards(&mut self, axis: Axis, direction: Direction) {
+        let order = self.focus_order();
+        let maybe_focused = self.focused_widget();
+        let origin = match maybe_focused.and_then(|idx| self.rect_of(idx)) {
+            Some(rect) => rect.xy(),
+            None => {
+                if let Some(&first) = order.first() {
+                    self.set_keyboard_focus(Some(first));
+                }
+                return;
+            },
+        };
+
+        let mut maybe_nearest: Option<(widget::Index, Scalar)> = None;
+        for idx in order {
+            if Some(idx) == maybe_focused {
+                continue;
+            }
+            let xy = match self.rect_of(idx) {
+                Some(rect) => rect.xy(),
+                None => continue,
+            };
+            let delta = utils::vec2_sub(xy, origin);
+            let (along, across) = match axis {
+                Axis::X => (delta[0], delta[1]),
+                Axis::Y => (delta[1], delta[0]),
+            };
+            let along = match direction {
+                Direction::Forwards => along,
+                Direction::Backwards => -along,
+            };
+            if along <= 0.0 {
+                continue;
+            }
+            let distance = along + across.abs() * 2.0;
+            if maybe_nearest.map_or(true, |(_, nearest)| distance < nearest) {
+                maybe_nearest = Some((idx, distance));
+            }
+        }
+
+        if let Some((idx, _)) = maybe_nearest {
+            self.set_keyboard_focus(Some(idx));
+        }
+    }
+
+    /// The focusable widgets in the order in which they receive keyboard focus.
+    ///
+    /// This is the set of widgets that were given a `Widget::tab_index` during the most recent
+    /// call to `Ui::set_widgets`.
+    pub fn focus_order(&self) -> Vec<widget::Index> {
+        let mut focusable_widgets = self.focusable_widgets.clone();
+        // The sort is stable, so widgets sharing a tab index remain in instantiation order.
+        focusable_widgets.sort_by_key(|&(tab_index, _)| tab_index);
+        focusable_widgets.into_iter().map(|(_, idx)| idx).collect()
+    }
+
+    /// The focusable widget that currently has keyboard focus, if there is one.
+    ///
+    /// This is either the widget capturing the keyboard or its nearest focusable ancestor, as
+    /// focusable widgets may pass keyboard capture on to one of their children.
+    pub fn focused_widget(&self) -> Option<widget::Index> {
+        let mut maybe_idx = self.global_input.current.widget_capturing_keyboard;
+        while let Some(idx) = maybe_idx {
+            if self.focusable_widgets.iter().any(|&(_, focusable)| focusable == idx) {
+                return Some(idx);
+            }
+            maybe_idx = self.widget_graph.depth_parent(idx);
+        }
+        None
+    }
+
+    // Move keyboard focus one step through the `focus_order` in the given direction.
+    //
+    // Returns whether or not focus moved to another widget.
+    fn step_focus(&mut self, forward: bool) -> bool {
+        let order = self.focus_order();
+        if order.is_empty() {
+            return false;
+        }
+        let maybe_position = self.focused_widget()
+            .and_then(|focused| order.iter().position(|&idx| idx == focused));
+        let next = match (maybe_position, forward) {
+            (Some(i), true) => (i + 1) % order.len(),
+            (Some(i), false) => (i + order.len() - 1) % order.len(),
+            (None, true) => 0,
+            (None, false) => order.len() - 1,
+        };
+        if maybe_position == Some(next) {
+            return false;
+        }
+        self.set_keyboard_focus(Some(order[next]));
+        true
+    }
+
+    /// Register a keyboard shortcut with the given `id`.
+    ///
+    /// Each time the shortcut is pressed, an `event::Ui::Shortcut` with the `id` is produced.
+    /// Registering a shortcut with the same `id` as an existing one replaces it.
+    pub fn register_shortcut(&mut self,
+                             id: input::shortcut::Id,
+                             shortcut: input::shortcut::Shortcut)
+    {
+        self.shortcuts.register(id, shortcut);
+    }
+
+    /// Remove the keyboard shortcut registered with the given `id`, if any.
+    pub fn unregister_shortcut(&mut self, id: input::shortcut::Id) {
+        self.shortcuts.unregister(id);
+    }
+
+    /// The keyboard shortcuts registered with the `Ui`.
+    pub fn shortcuts(&self) -> &input::shortcut::Registry {
+        &self.shortcuts
+    }
+
+    /// Whether or not the keyboard shortcut with the given `id` has been triggered since the last
+    /// call to `Ui::set_widgets`.
+    pub fn shortcut_triggered(&self, id: input::shortcut::Id) -> bool {
+        self.global_input.events().shortcuts().any(|triggered| triggered == id)
+    }
+
+    /// The payload of the drag-and-drop that is currently in progress, if any.
+    ///
+    /// This also returns the payload of a drag-and-drop that has been dropped but not yet accepted,
+    /// allowing the target to inspect it upon receiving the `Drop` event.
+    pub fn drag_and_drop_payload(&self) -> Option<&input::drag_and_drop::Payload> {
+        self.maybe_drag_and_drop.as_ref()
+            .or(self.maybe_dropped.as_ref())
+            .map(|dnd| &dnd.payload)
+    }
+
+    // Cancel the `Drag` of each mouse button that has been dragged beyond the
+    // `theme.mouse_drag_threshold` since it was pressed, along with any drag-and-drop under way.
+    //
+    // Each cancelled button is released from the `input::State` so that releasing it later
+    // produces no `Click` and the mouse is no longer pinned to the widget it was pressed over.
+    // Buttons that are merely held down are left untouched.
+    //
+    // Returns whether or not any mouse button was released.
+    fn cancel_drag(&mut self) -> bool {
+        let buttons = self.global_input.current.mouse.buttons.clone();
+        let mouse_xy = self.global_input.current.mouse.xy;
+        let is_dragging_and_dropping = self.maybe_drag_and_drop.is_some();
+        let mut was_released = false;
+        for (button, origin, widget) in buttons.pressed() {
+            let delta_xy = utils::vec2_sub(mouse_xy, origin);
+            let distance = (delta_xy[0] * delta_xy[0] + delta_xy[1] * delta_xy[1]).sqrt();
+            if distance <= self.theme.mouse_drag_threshold && !is_dragging_and_dropping {
+                continue;
+            }
+            let drag_cancelled = event::DragCancelled {
+                button: button,
+                modifiers: self.global_input.current.modifiers,
+            };
+            let event = event::Ui::DragCancelled(widget, drag_cancelled).into();
+            self.global_input.push_event(event);
+            self.global_input.current.mouse.buttons.release(button);
+            was_released = true;
+        }
+
+        if let Some(dnd) = self.maybe_drag_and_drop.take() {
+            if let Some(idx) = dnd.maybe_target {
+                let event = self.drag_and_drop_event(dnd.source, dnd.payload.kind());
+                self.global_input.push_event(event::Ui::DragLeave(idx, event).into());
+            }
+            let drag_end = event::DragEnd { target: None, accepted: false };
+            self.global_input.push_event(event::Ui::DragEnd(dnd.source, drag_end).into());
+        }
+
+        was_released
+    }
+
+    // Describe the drag-and-drop started by the given `source` at the current state of the mouse.
+    fn drag_and_drop_event(&self, source: widget::Index, kind: &'static str) -> event::DragAndDrop {
+        event::DragAndDrop {
+            source: source,
+            kind: kind,
+            xy: self.global_input.current.mouse.xy,
+            modifiers: self.global_input.current.modifiers,
+        }
+    }
+
+    // Produce the `DragLeave`, `DragEnter` and `DragOver` events for the drag-and-drop in progress
+    // after the mouse has moved.
+    fn drag_payload_over_widget_under_mouse(&mut self) {
+        let widget_under_mouse = self.global_input.current.widget_under_mouse;
+        let (event, maybe_prev_target) = match self.maybe_drag_and_drop {
+            Some(ref dnd) => (self.drag_and_drop_event(dnd.source, dnd.payload.kind()),
+                              dnd.maybe_target),
+            None => return,
+        };
+        if let Some(ref mut dnd) = self.maybe_drag_and_drop {
+            dnd.maybe_target = widget_under_mouse;
+        }
+        if maybe_prev_target != widget_under_mouse {
+            if let Some(idx) = maybe_prev_target {
+                self.global_input.push_event(event::Ui::DragLeave(idx, event).into());
+            }
+            if let Some(idx) = widget_under_mouse {
+                self.global_input.push_event(event::Ui::DragEnter(idx, event).into());
+            }
+        }
+        if let Some(idx) = widget_under_mouse {
+            self.global_input.push_event(event::Ui::DragOver(idx, event).into());
+        }
+    }
+
+    // Drop the payload of the drag-and-drop in progress upon the widget under the mouse.
+    //
+    // If there is no widget under the mouse, the drag-and-drop ends immediately.
+    fn drop_payload_on_widget_under_mouse(&mut self) {
+        let mut dnd = match self.maybe_drag_and_drop.take() {
+            Some(dnd) => dnd,
+            None => return,
+        };
+        match self.global_input.current.widget_under_mouse {
+            Some(target) => {
+                let event = self.drag_and_drop_event(dnd.source, dnd.payload.kind());
+                self.global_input.push_event(event::Ui::Drop(target, event).into());
+                dnd.maybe_target = Some(target);
+                self.maybe_dropped = Some(dnd);
+            },
+            None => {
+                let drag_end = event::DragEnd { target: None, accepted: false };
+                self.global_input.push_event(event::Ui::DragEnd(dnd.source, drag_end).into());
+            },
+        }
+    }
+
+
     /// A function within which all widgets are instantiated by the user, normally situated within
     /// the "update" stage of an event loop.
     pub fn set_widgets(&mut self) -> UiCell {
         self.maybe_prev_widget_idx = None;
         self.maybe_current_parent_idx = None;
+        self.caret_rect = None;
+        self.focusable_widgets.clear();
+        self.tab_consuming_widgets.clear();
+        self.key_consuming_widgets.clear();
+        self.text_input_widgets.clear();
+        self.mouse_cursors.clear();
+        self.handled_events.clear();
+        self.maybe_tooltip = None;
+
+        // Produce a `LongPress` for each finger that has been held in place for long enough.
+        {
+            let now = self.now();
+            let long_press_duration = self.theme.long_press_duration;
+            let mut long_presses = Vec::new();
+            for (&id, touch) in self.global_input.touch.iter_mut() {
+                if !touch.has_moved && !touch.is_long_press
+                && now.duration_since(touch.start_time) >= long_press_duration {
+                    touch.is_long_press = true;
+                    let long_press = event::LongPress { id: id, xy: touch.xy };
+                    long_presses.push(event::Ui::LongPress(touch.widget, long_press));
+                }
+            }
+            for long_press in long_presses {
+                self.global_input.push_event(long_press.into());
+            }
+        }
 
         // Move the previous `updated_widgets` to `prev_updated_widgets` and clear
         // `updated_widgets` so that we're ready to store the newly updated widgets.
@@ -1093,6 +2149,62 @@ impl<'a> UiCell<'a> {
         self.ui.kids_bounding_box(idx)
     }
 
+    /// The current contents of the `Ui`'s clipboard.
+    pub fn clipboard_contents(&mut self) -> Option<String> {
+        self.ui.clipboard.contents()
+    }
+
+    /// Replace the contents of the `Ui`'s clipboard with the given `String`.
+    pub fn set_clipboard_contents(&mut self, contents: String) {
+        self.ui.clipboard.set_contents(contents);
+    }
+
+    /// Report the area occupied by the text cursor of the widget capturing the keyboard.
+    ///
+    /// See `Ui::caret_rect` for details.
+    pub fn set_caret_rect(&mut self, rect: Rect) {
+        self.ui.caret_rect = Some(rect);
+    }
+
+    /// Display the given text within a **Tooltip** while the widget at the given index is hovered.
+    ///
+    /// Unlike `Widget::tooltip`, the text may be produced at runtime, i.e. to describe the widget's
+    /// current value. This must be called during each call to `Ui::set_widgets` for which it
+    /// applies, after the widget has been set.
+    pub fn set_tooltip<I, T>(&mut self, idx: I, text: T)
+        where I: Into<widget::Index>,
+              T: Into<String>,
+    {
+        let idx = idx.into();
+        if self.ui.maybe_hovered.map_or(false, |(hovered, _)| hovered == idx) {
+            self.ui.maybe_tooltip = Some(std::borrow::Cow::Owned(text.into()));
+        }
+    }
+
+    /// Request the given mouse cursor icon for while the mouse is over, or captured by, the widget
+    /// at the given index.
+    ///
+    /// This must be called during each call to `Ui::set_widgets` for which it applies. See
+    /// `Ui::mouse_cursor` for details.
+    pub fn set_mouse_cursor<I: Into<widget::Index>>(&mut self, idx: I, cursor: MouseCursor) {
+        self.ui.mouse_cursors.push((idx.into(), cursor));
+    }
+
+    /// Mark the given event, received by the widget at the given index, as handled.
+    ///
+    /// If `UiBuilder::bubble_events` is enabled, each `Text`, `Press`, `Release`, `Click`,
+    /// `DoubleClick`, `MultiClick`, `Drag`, `Scroll`, `Tap` and `LongPress` event that is not
+    /// marked as handled by the widget that received it bubbles up along the widget's depth
+    /// parents. Each ancestor whose input is requested later within the same call to
+    /// `Ui::set_widgets` receives it as an `event::Widget::Bubble`, and may in turn mark the
+    /// `Bubble` as handled to stop it from bubbling any further.
+    pub fn set_event_handled<I, E>(&mut self, idx: I, event: E)
+        where I: Into<widget::Index>,
+              E: Into<event::Widget>,
+    {
+        self.ui.handled_events.push((idx.into(), event.into()));
+    }
+
     /// Scroll the widget at the given index by the given offset amount.
     ///
     /// The produced `Scroll` event will be pushed to the `pending_scroll_events` and will be
@@ -1113,10 +2225,185 @@ impl<'a> UiCell<'a> {
         }
     }
 
+    /// Give the scrolling of the widget at the given index the given velocity in points per
+    /// second, along each axis.
+    ///
+    /// The produced `Fling` event will be pushed to the `pending_scroll_events` and will be
+    /// applied to the widget during the next call to `Ui::set_widgets`.
+    pub fn fling_widget<I>(&mut self, widget_idx: I, velocity: [Scalar; 2])
+        where I: Into<widget::Index>
+    {
+        if velocity[0] != 0.0 || velocity[1] != 0.0 {
+            let fling = event::Fling { velocity: velocity };
+            let event = event::Ui::Fling(Some(widget_idx.into()), fling);
+            self.ui.pending_scroll_events.push(event);
+        }
+    }
+
+    /// Give keyboard capture to the widget at the given index.
+    ///
+    /// The change is applied at the end of `Ui::set_widgets`, so that the widgets receive the
+    /// resulting `WidgetCapturesKeyboard` and `WidgetUncapturesKeyboard` events during the next
+    /// call to `Ui::set_widgets`. See `Ui::set_keyboard_focus` for details.
+    pub fn set_keyboard_focus<I: Into<widget::Index>>(&mut self, idx: I) {
+        self.ui.maybe_pending_keyboard_focus = Some(Some(idx.into()));
+    }
+
+    /// Indicates that the widget at the given index uses the `Tab` key itself while it captures
+    /// the keyboard, so `Tab` presses should not move keyboard focus away from it.
+    ///
+    /// Focus may still be moved away using `Ctrl+Tab`. This must be called during each call to
+    /// `Ui::set_widgets` for which it applies.
+    pub fn consume_tab<I: Into<widget::Index>>(&mut self, idx: I) {
+        self.ui.tab_consuming_widgets.push(idx.into());
+    }
+
+    /// Indicates that the widget at the given index uses the given key combination itself while it
+    /// captures the keyboard, so the key press should not trigger any registered shortcut.
+    ///
+    /// This must be called during each call to `Ui::set_widgets` for which it applies.
+    pub fn consume_key<I>(&mut self,
+                          idx: I,
+                          modifiers: input::keyboard::ModifierKey,
+                          key: input::Key)
+        where I: Into<widget::Index>,
+    {
+        let combo = input::shortcut::Combo::new(modifiers, key);
+        self.ui.key_consuming_widgets.push((idx.into(), combo));
+    }
+
+    /// Indicates that the widget at the given index uses each key combination bound to an editing
+    /// `Command` itself while it captures the keyboard (see `consume_key`).
+    ///
+    /// If no `KeyBindings` are given, those of the `Ui` are used (see `Ui::key_bindings`).
+    pub fn consume_bound_keys<I>(&mut self,
+                                 idx: I,
+                                 maybe_key_bindings: Option<&widget::text_edit::KeyBindings>)
+        where I: Into<widget::Index>,
+    {
+        let idx = idx.into();
+        let ui = &mut *self.ui;
+        let key_bindings = match maybe_key_bindings {
+            Some(key_bindings) => key_bindings,
+            None => ui.theme.key_bindings.as_ref().unwrap_or(&ui.platform_key_bindings),
+        };
+        for (&(key, modifiers), _) in key_bindings.bindings() {
+            let combo = input::shortcut::Combo::new(modifiers, key);
+            ui.key_consuming_widgets.push((idx, combo));
+        }
+    }
+
+    /// Indicates that the widget at the given index enters text while it captures the keyboard, so
+    /// character keys pressed without `Ctrl`, `Alt` or `Gui` should not trigger any registered
+    /// shortcut.
+    ///
+    /// This must be called during each call to `Ui::set_widgets` for which it applies.
+    pub fn consume_text_input<I: Into<widget::Index>>(&mut self, idx: I) {
+        self.ui.text_input_widgets.push(idx.into());
+    }
+
+    /// Start dragging the given `payload` from the widget at the given index.
+    ///
+    /// The drag-and-drop continues until the left mouse button is released, at which point the
+    /// payload is dropped upon the widget under the mouse. This does nothing if the left mouse
+    /// button is not down or if a drag-and-drop is already in progress.
+    pub fn start_drag_and_drop<I>(&mut self, source: I, payload: input::drag_and_drop::Payload)
+        where I: Into<widget::Index>,
+    {
+        let is_left_down = self.ui.global_input.current.mouse.buttons.left().is_down();
+        if !is_left_down || self.ui.maybe_drag_and_drop.is_some() {
+            return;
+        }
+        self.ui.maybe_drag_and_drop = Some(input::drag_and_drop::DragAndDrop {
+            source: source.into(),
+            payload: payload,
+            maybe_target: None,
+        });
+    }
+
+    /// Accept the payload that was dropped upon the widget at the given index.
+    ///
+    /// Returns `None` if no payload was dropped upon the widget since the last call to
+    /// `Ui::set_widgets`. The payload is rejected if it is not accepted before the end of this
+    /// call to `Ui::set_widgets`. Either way, the widget that started the drag-and-drop receives a
+    /// `DragEnd` event.
+    pub fn accept_drop<I>(&mut self, idx: I) -> Option<input::drag_and_drop::Payload>
+        where I: Into<widget::Index>,
+    {
+        let target = idx.into();
+        let is_target = self.ui.maybe_dropped.as_ref()
+            .map_or(false, |dnd| dnd.maybe_target == Some(target));
+        if !is_target {
+            return None;
+        }
+        self.ui.maybe_dropped.take().map(|dnd| {
+            let drag_end = event::DragEnd { target: Some(target), accepted: true };
+            self.ui.maybe_pending_drag_end = Some((dnd.source, drag_end));
+            dnd.payload
+        })
+    }
+
 }
 
 impl<'a> Drop for UiCell<'a> {
     fn drop(&mut self) {
+        // Draw the focus ring around the focused widget on top of its siblings.
+        if let Some(focused) = self.ui.focused_widget() {
+            let width = self.ui.theme.focus_ring_width;
+            let maybe_rect = self.ui.rect_of(focused);
+            let maybe_parent: Option<widget::Index> = self.ui.widget_graph.depth_parent(focused);
+            if let (Some(rect), Some(parent)) = (maybe_rect, maybe_parent) {
+                if width > 0.0 {
+                    use {Positionable, Widget};
+                    let color = self.ui.theme.focus_ring_color;
+                    let line_style = widget::line::Style::solid().color(color).thickness(width);
+                    let focus_ring = self.ui.focus_ring;
+                    // Pad outwards, as outlines are centred upon the edge of their `Rect`.
+                    widget::Rectangle::outline_styled(rect.pad(-width / 2.0).dim(), line_style)
+                        .xy(rect.xy())
+                        .parent(parent)
+                        .graphics_for(focused)
+                        .set(focus_ring, self);
+                }
+            }
+        }
+
+        // Display the tooltip of the hovered widget above all other widgets.
+        if let (Some(text), Some((hovered, mouse_xy))) =
+            (self.ui.maybe_tooltip.take(), self.ui.maybe_hovered)
+        {
+            use {Positionable, Sizeable};
+            let tooltip = widget::Tooltip::new(&text);
+            let dim = tooltip.get_wh(&*self.ui).unwrap_or([0.0, 0.0]);
+            let window_dim = [self.ui.win_w, self.ui.win_h];
+            let xy = widget::tooltip::position_near_mouse(mouse_xy, dim, window_dim);
+            let (window, tooltip_idx) = (self.ui.window, self.ui.tooltip);
+            tooltip
+                .xy(xy)
+                .parent(window)
+                .graphics_for(hovered)
+                .set(tooltip_idx, self);
+        }
+
+        // Display the preview of the payload being dragged near the mouse.
+        let maybe_preview = self.ui.maybe_drag_and_drop.as_ref().and_then(|dnd| {
+            dnd.payload.preview_text().map(|text| (dnd.source, text.to_string()))
+        });
+        if let Some((source, text)) = maybe_preview {
+            use {Positionable, Sizeable};
+            let preview = widget::Tooltip::new(&text);
+            let dim = preview.get_wh(&*self.ui).unwrap_or([0.0, 0.0]);
+            let mouse_xy = self.ui.global_input.current.mouse.xy;
+            let window_dim = [self.ui.win_w, self.ui.win_h];
+            let xy = widget::tooltip::position_near_mouse(mouse_xy, dim, window_dim);
+            let (window, drag_preview) = (self.ui.window, self.ui.drag_preview);
+            preview
+                .xy(xy)
+                .parent(window)
+                .graphics_for(source)
+                .set(drag_preview, self);
+        }
+
         // We'll need to re-draw if we have gained or lost widgets.
         if self.ui.updated_widgets != self.ui.prev_updated_widgets {
             self.ui.needs_redraw();
@@ -1138,11 +2425,29 @@ impl<'a> Drop for UiCell<'a> {
         // Reset the global input state. Note that this is the **only** time this should be called.
         self.ui.global_input.clear_events_and_update_start_state();
 
+        // All widgets have stepped their scroll animations by the elapsed time.
+        self.ui.animation_secs = 0.0;
+
         // Move all pending `Scroll` events that have been produced since the start of this method
         // into the `global_input` event buffer.
         for scroll_event in self.ui.pending_scroll_events.drain(0..) {
             self.ui.global_input.push_event(scroll_event.into());
         }
+
+        // End any drag-and-drop whose payload was dropped, letting the source know whether or not
+        // the payload was accepted.
+        if let Some(dnd) = self.ui.maybe_dropped.take() {
+            let drag_end = event::DragEnd { target: dnd.maybe_target, accepted: false };
+            self.ui.maybe_pending_drag_end = Some((dnd.source, drag_end));
+        }
+        if let Some((source, drag_end)) = self.ui.maybe_pending_drag_end.take() {
+            self.ui.global_input.push_event(event::Ui::DragEnd(source, drag_end).into());
+        }
+
+        // Apply any keyboard focus that was requested since the start of this method.
+        if let Some(maybe_idx) = self.ui.maybe_pending_keyboard_focus.take() {
+            self.ui.set_keyboard_focus(maybe_idx);
+        }
     }
 }
 
@@ -1214,6 +2519,14 @@ pub fn pre_update_cache(ui: &mut Ui, widget: widget::PreUpdateCache) {
     ui.maybe_prev_widget_idx = Some(widget.idx);
     ui.maybe_current_parent_idx = widget.maybe_parent_idx;
     let widget_idx = widget.idx;
+    if let Some(tab_index) = widget.maybe_tab_index {
+        ui.focusable_widgets.push((tab_index, widget_idx));
+    }
+    if let Some(text) = widget.maybe_tooltip {
+        if ui.maybe_hovered.map_or(false, |(idx, _)| idx == widget_idx) {
+            ui.maybe_tooltip = Some(text.into());
+        }
+    }
     ui.widget_graph.pre_update_cache(ui.window, widget, ui.updated_widgets.len());
 
     // Add the widget's `NodeIndex` to the set of updated widgets.
@@ -1233,3 +2546,9 @@ pub fn post_update_cache<W>(ui: &mut Ui, widget: widget::PostUpdateCache<W>)
     ui.maybe_current_parent_idx = widget.maybe_parent_idx;
     ui.widget_graph.post_update_cache(widget);
 }
+
+/// The time by which scroll animations should be stepped during the current call to
+/// `Ui::set_widgets`.
+pub fn animation_secs(ui: &Ui) -> f64 {
+    ui.animation_secs
+}
@@ -13,6 +13,7 @@ use {
     UiCell,
     Widget,
 };
+use input;
 use widget;
 
 
@@ -245,10 +246,26 @@ impl<'a, S> Widget for Button<'a, S>
                     color.highlighted()
                 }
             });
-            let times_clicked = input.clicks().left().count() as u16;
+            // A focused button may also be pressed via `Space` or `Return`.
+            let times_activated = input.presses().key()
+                .filter(|press| match press.key {
+                    input::Key::Space | input::Key::Return => true,
+                    _ => false,
+                })
+                .count();
+            let times_clicked = (input.clicks().left().count() + times_activated) as u16;
             (color, times_clicked)
         };
 
+        // The button acts upon the left mouse button along with `Space` and `Return`, so stop those
+        // events from bubbling up to the button's parent.
+        let handled_events: Vec<_> = ui.widget_input(idx).events()
+            .filter(|event| widget::is_activation_event(event))
+            .collect();
+        for event in handled_events {
+            ui.set_event_handled(idx, event);
+        }
+
         // BorderedRectangle widget.
         let rectangle_idx = state.rectangle_idx.get(&mut ui);
         let dim = rect.dim();
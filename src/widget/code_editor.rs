@@ -0,0 +1,524 @@
+//! A widget for displaying and editing source code.
+
+use {
+    Align,
+    Color,
+    Colorable,
+    FontSize,
+    NodeIndex,
+    Positionable,
+    Range,
+    Rect,
+    Scalar,
+    Sizeable,
+    Widget,
+};
+use graph;
+use std;
+use text;
+use widget;
+use widget::text_edit::{Cursor, KeyBindings};
+
+
+/// A multi-line `TextEdit` for editing source code.
+///
+/// Each line is numbered within a gutter that scrolls along with the text and the line containing
+/// the cursor is highlighted. `Tab` and `Shift+Tab` indent and outdent the selected lines, while
+/// `Return` carries the indentation of the current line over to the new line.
+///
+/// The text is drawn with the `Theme`'s `monospace_font_id` by default, falling back to its
+/// `font_id` if it has none.
+pub struct CodeEditor<'a> {
+    common: widget::CommonBuilder,
+    text: &'a str,
+    style: Style,
+    maybe_key_bindings: Option<&'a KeyBindings>,
+    indent: &'a str,
+    match_brackets: bool,
+}
+
+widget_style!{
+    /// Unique graphical styling for the CodeEditor.
+    style Style {
+        /// The color of the background behind the text.
+        - color: Color { theme.shape_color }
+        /// The color of the text, cursor and selection.
+        - text_color: Color { theme.label_color }
+        /// The font size of the text and line numbers.
+        - font_size: FontSize { theme.font_size_small }
+        /// The vertical space between each line of text.
+        - line_spacing: Scalar { 1.0 }
+        /// The font used for the text and line numbers.
+        ///
+        /// If `None`, the `Theme`'s `font_id` is used.
+        - font_id: Option<text::font::Id> { theme.monospace_font_id }
+        /// The color of the gutter behind the line numbers.
+        ///
+        /// If `None`, a highlighted version of the `color` is used.
+        - gutter_color: Option<Color> { None }
+        /// The color of the line numbers.
+        ///
+        /// If `None`, the `text_color` is used with a reduced alpha.
+        - gutter_label_color: Option<Color> { None }
+        /// The gap between the line numbers and the edges of the gutter.
+        - gutter_padding: Scalar { 6.0 }
+        /// The color drawn over the line containing the cursor.
+        ///
+        /// If `None`, the `text_color` is used with a low alpha.
+        - current_line_color: Option<Color> { None }
+        /// The color drawn over the bracket at the cursor and its matching bracket.
+        ///
+        /// If `None`, the `text_color` is used with a reduced alpha.
+        - bracket_color: Option<Color> { None }
+    }
+}
+
+/// The State of the CodeEditor widget that will be cached within the Ui.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    rectangle_idx: widget::IndexSlot,
+    gutter_idx: widget::IndexSlot,
+    text_edit_idx: widget::IndexSlot,
+    current_line_idx: widget::IndexSlot,
+    open_bracket_idx: widget::IndexSlot,
+    close_bracket_idx: widget::IndexSlot,
+    /// Indices for the `Text` widgets used to draw each visible line number.
+    line_number_indices: Vec<NodeIndex>,
+}
+
+
+impl<'a> CodeEditor<'a> {
+
+    /// Construct a CodeEditor widget.
+    pub fn new(text: &'a str) -> Self {
+        CodeEditor {
+            common: widget::CommonBuilder::new(),
+            text: text,
+            style: Style::new(),
+            maybe_key_bindings: None,
+            indent: "    ",
+            match_brackets: false,
+        }.crop_kids()
+    }
+
+    /// The string inserted by each level of indentation.
+    ///
+    /// By default, this is four spaces.
+    pub fn indent(mut self, indent: &'a str) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Use the given `KeyBindings` to map key presses to editing `Command`s.
+    ///
+    /// `Tab` and `Shift+Tab` are bound to `Indent` and `Outdent` unless the given bindings
+    /// already bind `Tab`.
+    pub fn key_bindings(mut self, key_bindings: &'a KeyBindings) -> Self {
+        self.maybe_key_bindings = Some(key_bindings);
+        self
+    }
+
+    /// The font used for the text and line numbers.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.font_id = Some(Some(font_id));
+        self
+    }
+
+    /// The color of the gutter behind the line numbers.
+    pub fn gutter_color(mut self, color: Color) -> Self {
+        self.style.gutter_color = Some(Some(color));
+        self
+    }
+
+    /// The color of the line numbers.
+    pub fn gutter_label_color(mut self, color: Color) -> Self {
+        self.style.gutter_label_color = Some(Some(color));
+        self
+    }
+
+    /// The color drawn over the line containing the cursor.
+    pub fn current_line_color(mut self, color: Color) -> Self {
+        self.style.current_line_color = Some(Some(color));
+        self
+    }
+
+    /// The color drawn over the bracket at the cursor and its matching bracket.
+    pub fn bracket_color(mut self, color: Color) -> Self {
+        self.style.bracket_color = Some(Some(color));
+        self
+    }
+
+    builder_methods!{
+        pub text_color { style.text_color = Some(Color) }
+        pub font_size { style.font_size = Some(FontSize) }
+        pub line_spacing { style.line_spacing = Some(Scalar) }
+        pub gutter_padding { style.gutter_padding = Some(Scalar) }
+        pub match_brackets { match_brackets = bool }
+    }
+
+}
+
+
+impl<'a> Widget for CodeEditor<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Option<String>;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self) -> State {
+        State {
+            rectangle_idx: widget::IndexSlot::new(),
+            gutter_idx: widget::IndexSlot::new(),
+            text_edit_idx: widget::IndexSlot::new(),
+            current_line_idx: widget::IndexSlot::new(),
+            open_bracket_idx: widget::IndexSlot::new(),
+            close_bracket_idx: widget::IndexSlot::new(),
+            line_number_indices: Vec::new(),
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the CodeEditor.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { idx, state, rect, style, mut ui, .. } = args;
+        let CodeEditor { text, maybe_key_bindings, indent, match_brackets, .. } = self;
+
+        // Retrieve the `font_id`, as long as a valid `Font` for it still exists.
+        //
+        // If we've no font to use for text logic, bail out without updating.
+        let font_id = match style.font_id(&ui.theme)
+            .or(ui.theme.font_id)
+            .or(ui.fonts.ids().next())
+            .and_then(|id| ui.fonts.get(id).map(|_| id))
+        {
+            Some(font_id) => font_id,
+            None => return None,
+        };
+
+        let color = style.color(ui.theme());
+        let text_color = style.text_color(ui.theme());
+        let font_size = style.font_size(ui.theme());
+        let line_spacing = style.line_spacing(ui.theme());
+        let gutter_padding = style.gutter_padding(ui.theme());
+        let gutter_color = style.gutter_color(ui.theme())
+            .unwrap_or_else(|| color.highlighted());
+        let gutter_label_color = style.gutter_label_color(ui.theme())
+            .unwrap_or_else(|| text_color.alpha(0.5));
+        let current_line_color = style.current_line_color(ui.theme())
+            .unwrap_or_else(|| text_color.alpha(0.08));
+        let bracket_color = style.bracket_color(ui.theme())
+            .unwrap_or_else(|| text_color.alpha(0.25));
+
+        let rectangle_idx = state.rectangle_idx.get(&mut ui);
+        widget::Rectangle::fill(rect.dim())
+            .xy(rect.xy())
+            .color(color)
+            .graphics_for(idx)
+            .parent(idx)
+            .set(rectangle_idx, &mut ui);
+
+        // The gutter is wide enough for the digits of the greatest line number.
+        let gutter_rect = {
+            let num_lines = text.matches('\n').count() + 1;
+            let digits: String = std::iter::repeat('8').take(num_lines.to_string().len()).collect();
+            let font = ui.fonts.get(font_id).unwrap();
+            let w = text::line::width(&digits, font, font_size) + gutter_padding * 2.0;
+            Rect { x: Range::new(0.0, w).align_start_of(rect.x), y: rect.y }
+        };
+        let text_edit_rect = Rect {
+            x: Range::new(gutter_rect.right() + gutter_padding, rect.right()),
+            y: rect.y,
+        };
+
+        let text_edit_idx = state.text_edit_idx.get(&mut ui);
+
+        // When focused, pass keyboard capture on to the `TextEdit` so that it may be edited.
+        if ui.global_input().current.widget_capturing_keyboard == Some(idx) {
+            ui.set_keyboard_focus(text_edit_idx);
+        }
+
+        let new_text = widget::TextEdit::new(text)
+            .wh(text_edit_rect.dim())
+            .xy(text_edit_rect.xy())
+            .font_id(font_id)
+            .font_size(font_size)
+            .line_spacing(line_spacing)
+            .color(text_color)
+            .align_text_left()
+            .align_text_top()
+            .restrict_to_height(false)
+            .indent(indent)
+            .auto_indent(true)
+            .tab_indents(true)
+            .and_then(maybe_key_bindings, |text_edit, kb| text_edit.key_bindings(kb))
+            .parent(idx)
+            .set(text_edit_idx, &mut ui);
+
+        // The cursor and the layout of the text as determined by the `TextEdit`.
+        let maybe_layout = ui.widget_graph()
+            .widget(text_edit_idx)
+            .and_then(|container| container.unique_widget_state::<widget::TextEdit>())
+            .map(|&graph::UniqueWidgetState { ref state, .. }| {
+                (state.cursor(), state.line_infos().to_vec())
+            });
+        let (cursor, line_infos) = match maybe_layout {
+            Some(layout) => layout,
+            None => return new_text,
+        };
+        let y_scroll_offset = ui.widget_graph()
+            .widget(text_edit_idx)
+            .and_then(|container| container.maybe_y_scroll_state)
+            .map(|scroll| scroll.offset)
+            .unwrap_or(0.0);
+
+        // The text is aligned to the top of the `TextEdit` and shifted by its scroll offset.
+        let text_y = {
+            let height = text::height(line_infos.len(), font_size, line_spacing);
+            Range::new(0.0, height).align_end_of(text_edit_rect.y).shift(y_scroll_offset)
+        };
+
+        // The *y* range occupied by the line at the given index.
+        let line_y = |i: usize| -> Range {
+            let top = text_y.end - i as Scalar * (font_size as Scalar + line_spacing);
+            Range::new(top - font_size as Scalar, top)
+        };
+
+        // Whether or not the line at the given index is the first of a line of text, rather than
+        // one that has been wrapped.
+        let starts_line = |i: usize| -> bool {
+            i == 0 || match line_infos[i - 1].end_break {
+                text::line::Break::Newline { .. } => true,
+                _ => false,
+            }
+        };
+
+        // Input over the gutter is given to the `TextEdit` so that the text may be scrolled and
+        // clicked lines selected from there.
+        let gutter_idx = state.gutter_idx.get(&mut ui);
+        widget::Rectangle::fill(gutter_rect.dim())
+            .xy(gutter_rect.xy())
+            .color(gutter_color)
+            .graphics_for(text_edit_idx)
+            .parent(idx)
+            .set(gutter_idx, &mut ui);
+
+        // Number each line of text, skipping those that are scrolled out of view.
+        let label_x = gutter_rect.x.pad(gutter_padding);
+        let mut line_number = 0;
+        let mut num_labels = 0;
+        for i in 0..line_infos.len() {
+            if !starts_line(i) {
+                continue;
+            }
+            line_number += 1;
+
+            let y = line_y(i);
+            if y.start > rect.top() {
+                continue;
+            }
+            if y.end < rect.bottom() {
+                break;
+            }
+
+            if num_labels == state.line_number_indices.len() {
+                state.update(|state| state.line_number_indices.push(ui.new_unique_node_index()));
+            }
+            let line_number_idx = state.line_number_indices[num_labels];
+            num_labels += 1;
+
+            let label = line_number.to_string();
+            widget::Text::new(&label)
+                .w(label_x.len())
+                .x_y(label_x.middle(), y.middle())
+                .font_id(font_id)
+                .font_size(font_size)
+                .color(gutter_label_color)
+                .align_text_right()
+                .no_line_wrap()
+                .graphics_for(text_edit_idx)
+                .parent(idx)
+                .set(line_number_idx, &mut ui);
+        }
+
+        if line_infos.is_empty() {
+            return new_text;
+        }
+
+        // Highlight each line that makes up the line of text containing the cursor.
+        let cursor_line = match cursor {
+            Cursor::Idx(cursor_idx) => cursor_idx.line,
+            Cursor::Selection { end, .. } => end.line,
+        };
+        let cursor_line = std::cmp::min(cursor_line, line_infos.len() - 1);
+        let first_line = (0..cursor_line + 1).rev().find(|&i| starts_line(i)).unwrap_or(0);
+        let last_line = (cursor_line + 1..line_infos.len())
+            .find(|&i| starts_line(i))
+            .map(|i| i - 1)
+            .unwrap_or(line_infos.len() - 1);
+        let current_line_rect = Rect {
+            x: text_edit_rect.x,
+            y: Range::new(line_y(last_line).start, line_y(first_line).end),
+        };
+        let current_line_idx = state.current_line_idx.get(&mut ui);
+        widget::Rectangle::fill(current_line_rect.dim())
+            .xy(current_line_rect.xy())
+            .color(current_line_color)
+            .graphics_for(text_edit_idx)
+            .parent(idx)
+            .set(current_line_idx, &mut ui);
+
+        // Highlight the bracket at the cursor along with its matching bracket.
+        let cursor_idx = match (match_brackets, cursor) {
+            (true, Cursor::Idx(cursor_idx)) => cursor_idx,
+            _ => return new_text,
+        };
+        let shown: &str = match new_text {
+            Some(ref new_text) => new_text,
+            None => text,
+        };
+        let char_idx = text::glyph::index_after_cursor(line_infos.iter().cloned(), cursor_idx)
+            .unwrap_or(0);
+        let (open, close) = match matching_brackets(shown, char_idx) {
+            Some(brackets) => brackets,
+            None => return new_text,
+        };
+
+        let bracket_rects = {
+            let font = ui.fonts.get(font_id).unwrap();
+            let text_rect = Rect { x: text_edit_rect.x, y: text_y };
+            let xy_at = |idx: text::cursor::Index| -> Option<(Scalar, Range)> {
+                let xys_per_line = text::cursor::xys_per_line_from_text(shown, &line_infos, font,
+                                                                        font_size, Align::Start,
+                                                                        Align::End, line_spacing,
+                                                                        text_rect);
+                text::cursor::xy_at(xys_per_line, idx)
+            };
+            let char_rect = |char_idx: usize| -> Option<Rect> {
+                char_cursor_index(&line_infos, char_idx).and_then(|start| {
+                    let end = text::cursor::Index { line: start.line, char: start.char + 1 };
+                    match (xy_at(start), xy_at(end)) {
+                        (Some((left, y)), Some((right, _))) =>
+                            Some(Rect { x: Range::new(left, right), y: y }),
+                        _ => None,
+                    }
+                })
+            };
+            [char_rect(open), char_rect(close)]
+        };
+
+        let bracket_indices = [state.open_bracket_idx.get(&mut ui),
+                               state.close_bracket_idx.get(&mut ui)];
+        for (maybe_rect, &bracket_idx) in bracket_rects.iter().zip(bracket_indices.iter()) {
+            if let Some(bracket_rect) = *maybe_rect {
+                widget::Rectangle::fill(bracket_rect.dim())
+                    .xy(bracket_rect.xy())
+                    .color(bracket_color)
+                    .graphics_for(text_edit_idx)
+                    .parent(idx)
+                    .set(bracket_idx, &mut ui);
+            }
+        }
+
+        new_text
+    }
+
+}
+
+
+impl<'a> Colorable for CodeEditor<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+/// The pairs of brackets that may be matched.
+const BRACKETS: &'static [(char, char)] = &[('(', ')'), ('[', ']'), ('{', '}')];
+
+/// The char indices of the bracket at the given char index and its matching bracket, in order.
+///
+/// The bracket following the cursor takes precedence over the bracket preceding it. Returns
+/// `None` if neither is a bracket with a match.
+fn matching_brackets(text: &str, char_idx: usize) -> Option<(usize, usize)> {
+    let chars: Vec<char> = text.chars().collect();
+
+    // Search from the bracket at `i` in the given direction for its match.
+    let find_match = |i: usize, bracket: char, other: char, forward: bool| -> Option<usize> {
+        let mut depth = 0;
+        let mut j = i;
+        loop {
+            if chars[j] == bracket {
+                depth += 1;
+            } else if chars[j] == other {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(j);
+                }
+            }
+            if forward {
+                j += 1;
+                if j == chars.len() {
+                    return None;
+                }
+            } else {
+                if j == 0 {
+                    return None;
+                }
+                j -= 1;
+            }
+        }
+    };
+
+    let after = chars.get(char_idx).map(|&c| (char_idx, c));
+    let before = char_idx.checked_sub(1).and_then(|i| chars.get(i).map(|&c| (i, c)));
+    for (i, c) in after.into_iter().chain(before) {
+        for &(open, close) in BRACKETS {
+            if c == open {
+                if let Some(j) = find_match(i, open, close, true) {
+                    return Some((i, j));
+                }
+            } else if c == close {
+                if let Some(j) = find_match(i, close, open, false) {
+                    return Some((j, i));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The cursor index immediately before the char at the given index.
+///
+/// Unlike `text::cursor::index_before_char`, a char at the start of a wrapped line is always
+/// considered part of that line, rather than the end of the previous line.
+fn char_cursor_index(line_infos: &[text::line::Info], char_idx: usize)
+    -> Option<text::cursor::Index>
+{
+    line_infos.iter()
+        .position(|info| info.start_char <= char_idx && char_idx < info.end_char())
+        .map(|line| {
+            let char = char_idx - line_infos[line].start_char;
+            text::cursor::Index { line: line, char: char }
+        })
+}
+
+
+#[test]
+fn brackets_match_across_nesting() {
+    let text = "f(a[0], {b})";
+    assert_eq!(matching_brackets(text, 1), Some((1, 11)));
+    assert_eq!(matching_brackets(text, 12), Some((1, 11)));
+    assert_eq!(matching_brackets(text, 4), Some((3, 5)));
+    assert_eq!(matching_brackets(text, 9), Some((8, 10)));
+    assert_eq!(matching_brackets(text, 7), None);
+    assert_eq!(matching_brackets("(]", 0), None);
+    assert_eq!(matching_brackets("", 0), None);
+}
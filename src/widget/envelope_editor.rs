@@ -16,7 +16,7 @@ use {
     Sizeable,
     Widget,
 };
-use num::Float;
+use num::{Float, NumCast};
 use std;
 use utils::{clamp, map_range, percentage, val_to_string};
 use widget;
@@ -67,6 +67,9 @@ widget_style!{
 #[derive(Clone, Debug, PartialEq)]
 pub struct State {
     pressed_point: Option<usize>,
+    /// The index, `x` and `y` of the pressed point at the time it was pressed, restored if the drag
+    /// is cancelled.
+    pre_drag: Option<(usize, Scalar, Scalar)>,
     rectangle_idx: widget::IndexSlot,
     label_idx: widget::IndexSlot,
     value_label_idx: widget::IndexSlot,
@@ -234,6 +237,7 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
     fn init_state(&self) -> Self::State {
         State {
             pressed_point: None,
+            pre_drag: None,
             rectangle_idx: widget::IndexSlot::new(),
             label_idx: widget::IndexSlot::new(),
             value_label_idx: widget::IndexSlot::new(),
@@ -312,6 +316,7 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
 
         // Track the currently pressed point if any.
         let mut pressed_point = state.pressed_point;
+        let mut pre_drag = state.pre_drag;
 
         // Handle all events that have occurred to the EnvelopeEditor since the last update.
         //
@@ -395,9 +400,13 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
                     if let event::Button::Mouse(MouseButton::Left, xy) = press.button {
                         // Check for a point under the cursor.
                         if let Some(idx) = point_under_rel_xy(&env, xy) {
+                            let x = env[idx].get_x().to_f64().unwrap();
+                            let y = env[idx].get_y().to_f64().unwrap();
                             pressed_point = Some(idx);
+                            pre_drag = Some((idx, x, y));
                         } else if pressed_point.is_some() {
                             pressed_point = None;
+                            pre_drag = None;
                         }
                     }
                 },
@@ -406,9 +415,21 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
                 event::Widget::Release(release) => {
                     if let event::Button::Mouse(MouseButton::Left, _) = release.button {
                         pressed_point = None;
+                        pre_drag = None;
                     }
                 },
 
+                // A cancelled left `Drag` moves the `pressed_point` back to where it was pressed.
+                event::Widget::DragCancelled(drag) if drag.button == MouseButton::Left => {
+                    if let Some((idx, x, y)) = pre_drag.take() {
+                        let x: E::X = NumCast::from(x).unwrap();
+                        let y: E::Y = NumCast::from(y).unwrap();
+                        let event = Event::MovePoint { i: idx, x: x, y: y };
+                        events.push(event);
+                    }
+                    pressed_point = None;
+                },
+
                 // A left `Drag` moves the `pressed_point` if there is one.
                 event::Widget::Drag(drag) if drag.button == input::MouseButton::Left => {
                     if let Some(idx) = pressed_point {
@@ -431,8 +452,11 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
             }
         }
 
-        if state.pressed_point != pressed_point {
-            state.update(|state| state.pressed_point = pressed_point);
+        if state.pressed_point != pressed_point || state.pre_drag != pre_drag {
+            state.update(|state| {
+                state.pressed_point = pressed_point;
+                state.pre_drag = pre_drag;
+            });
         }
 
         // Ensure that the local version of the `env` is up to date for drawing.
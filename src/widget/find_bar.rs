@@ -0,0 +1,328 @@
+//! A bar for searching and replacing text within a `TextEdit`.
+
+use {
+    Color,
+    Colorable,
+    FontSize,
+    Borderable,
+    Labelable,
+    Positionable,
+    Range,
+    Rect,
+    Scalar,
+    Sizeable,
+    Widget,
+};
+use widget;
+use widget::text_edit::Case;
+
+
+/// A bar for entering a `find::Query` and an optional replacement for a `TextEdit`.
+///
+/// The `FindBar` does not mutate the `TextEdit` itself. Rather, the produced `Event`s should be
+/// used to update the user's state, which may then be given to the `TextEdit` via its `find`,
+/// `find_next`, `find_previous`, `replace` and `replace_all` builder methods.
+///
+/// Pressing `Enter` within the query field produces a `Next` event.
+pub struct FindBar<'a> {
+    common: widget::CommonBuilder,
+    style: Style,
+    query: &'a str,
+    case: Case,
+    maybe_replacement: Option<&'a str>,
+}
+
+widget_style!{
+    /// Unique styling for the `FindBar`.
+    style Style {
+        /// The color of the bar's background.
+        - color: Color { theme.background_color }
+        /// The width of the border surrounding the bar and its fields.
+        - border: Scalar { theme.border_width }
+        /// The color of the borders.
+        - border_color: Color { theme.border_color }
+        /// The color of the text and button labels.
+        - label_color: Color { theme.label_color }
+        /// The font size of the text and button labels.
+        - font_size: FontSize { theme.font_size_small }
+        /// The height of each row of fields and buttons.
+        - row_height: Scalar { 24.0 }
+        /// The gap between the fields and buttons, and between them and the edge of the bar.
+        - spacing: Scalar { 4.0 }
+    }
+}
+
+/// The state of the `FindBar` that will be cached within the `Ui`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    rectangle_idx: widget::IndexSlot,
+    query_idx: widget::IndexSlot,
+    case_idx: widget::IndexSlot,
+    previous_idx: widget::IndexSlot,
+    next_idx: widget::IndexSlot,
+    replacement_idx: widget::IndexSlot,
+    replace_idx: widget::IndexSlot,
+    replace_all_idx: widget::IndexSlot,
+}
+
+/// Events produced by the `FindBar`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// The query string was edited.
+    Query(String),
+    /// The case sensitivity of the query was toggled.
+    Case(Case),
+    /// Select the next match.
+    Next,
+    /// Select the previous match.
+    Previous,
+    /// The replacement string was edited.
+    Replacement(String),
+    /// Replace the selected match.
+    Replace,
+    /// Replace every match.
+    ReplaceAll,
+}
+
+
+impl<'a> FindBar<'a> {
+
+    /// Construct a `FindBar` for the given query.
+    pub fn new(query: &'a str, case: Case) -> Self {
+        FindBar {
+            common: widget::CommonBuilder::new(),
+            style: Style::new(),
+            query: query,
+            case: case,
+            maybe_replacement: None,
+        }
+    }
+
+    /// Also show a field for the given replacement string along with buttons for replacing the
+    /// selected match and every match.
+    pub fn replacement(mut self, replacement: &'a str) -> Self {
+        self.maybe_replacement = Some(replacement);
+        self
+    }
+
+    builder_methods!{
+        pub label_color { style.label_color = Some(Color) }
+        pub font_size { style.font_size = Some(FontSize) }
+        pub row_height { style.row_height = Some(Scalar) }
+        pub spacing { style.spacing = Some(Scalar) }
+    }
+
+}
+
+
+impl<'a> Widget for FindBar<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Vec<Event>;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self) -> State {
+        State {
+            rectangle_idx: widget::IndexSlot::new(),
+            query_idx: widget::IndexSlot::new(),
+            case_idx: widget::IndexSlot::new(),
+            previous_idx: widget::IndexSlot::new(),
+            next_idx: widget::IndexSlot::new(),
+            replacement_idx: widget::IndexSlot::new(),
+            replace_idx: widget::IndexSlot::new(),
+            replace_all_idx: widget::IndexSlot::new(),
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the FindBar.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { idx, state, rect, style, mut ui, .. } = args;
+        let FindBar { query, case, maybe_replacement, .. } = self;
+
+        let color = style.color(ui.theme());
+        let border = style.border(ui.theme());
+        let border_color = style.border_color(ui.theme());
+        let label_color = style.label_color(ui.theme());
+        let font_size = style.font_size(ui.theme());
+        let row_height = style.row_height(ui.theme());
+        let spacing = style.spacing(ui.theme());
+
+        let rectangle_idx = state.rectangle_idx.get(&mut ui);
+        widget::BorderedRectangle::new(rect.dim())
+            .xy(rect.xy())
+            .graphics_for(idx)
+            .parent(idx)
+            .color(color)
+            .border(border)
+            .border_color(border_color)
+            .set(rectangle_idx, &mut ui);
+
+        // Splits the given row into a field that fills the remaining width followed by the given
+        // number of buttons with the given width.
+        let split_row = |row_y: Range, button_w: Scalar, num_buttons: usize| -> (Rect, Vec<Rect>) {
+            let inner_x = rect.x.pad(spacing);
+            let buttons_w = (button_w + spacing) * num_buttons as Scalar;
+            let field_x = Range::new(inner_x.start, (inner_x.end - buttons_w).max(inner_x.start));
+            let buttons = (0..num_buttons).map(|i| {
+                let start = field_x.end + spacing + (button_w + spacing) * i as Scalar;
+                Rect { x: Range::new(start, start + button_w), y: row_y }
+            }).collect();
+            (Rect { x: field_x, y: row_y }, buttons)
+        };
+
+        let first_row_y = Range::new(rect.top() - spacing - row_height, rect.top() - spacing);
+        let second_row_y = first_row_y.shift(-(row_height + spacing));
+
+        let mut events = Vec::new();
+
+        // The query field along with the case, previous and next buttons.
+        let (query_rect, buttons) = split_row(first_row_y, row_height, 3);
+
+        let query_idx = state.query_idx.get(&mut ui);
+        for event in widget::TextBox::new(query)
+            .wh(query_rect.dim())
+            .xy(query_rect.xy())
+            .font_size(font_size)
+            .text_color(label_color)
+            .border(border)
+            .border_color(border_color)
+            .placeholder("Find")
+            .parent(idx)
+            .set(query_idx, &mut ui)
+        {
+            match event {
+                widget::text_box::Event::Update(string) => events.push(Event::Query(string)),
+                widget::text_box::Event::Enter => events.push(Event::Next),
+                _ => (),
+            }
+        }
+
+        let case_idx = state.case_idx.get(&mut ui);
+        let is_case_sensitive = case == Case::Sensitive;
+        for is_case_sensitive in widget::Toggle::new(is_case_sensitive)
+            .wh(buttons[0].dim())
+            .xy(buttons[0].xy())
+            .label("Aa")
+            .label_color(label_color)
+            .label_font_size(font_size)
+            .border(border)
+            .border_color(border_color)
+            .parent(idx)
+            .set(case_idx, &mut ui)
+        {
+            let case = if is_case_sensitive { Case::Sensitive } else { Case::Insensitive };
+            events.push(Event::Case(case));
+        }
+
+        let previous_idx = state.previous_idx.get(&mut ui);
+        for _click in widget::Button::new()
+            .wh(buttons[1].dim())
+            .xy(buttons[1].xy())
+            .label("<")
+            .label_color(label_color)
+            .label_font_size(font_size)
+            .border(border)
+            .border_color(border_color)
+            .parent(idx)
+            .set(previous_idx, &mut ui)
+        {
+            events.push(Event::Previous);
+        }
+
+        let next_idx = state.next_idx.get(&mut ui);
+        for _click in widget::Button::new()
+            .wh(buttons[2].dim())
+            .xy(buttons[2].xy())
+            .label(">")
+            .label_color(label_color)
+            .label_font_size(font_size)
+            .border(border)
+            .border_color(border_color)
+            .parent(idx)
+            .set(next_idx, &mut ui)
+        {
+            events.push(Event::Next);
+        }
+
+        // The replacement field along with the replace and replace all buttons.
+        if let Some(replacement) = maybe_replacement {
+            let (replacement_rect, buttons) = split_row(second_row_y, row_height * 2.5, 2);
+
+            let replacement_idx = state.replacement_idx.get(&mut ui);
+            for event in widget::TextBox::new(replacement)
+                .wh(replacement_rect.dim())
+                .xy(replacement_rect.xy())
+                .font_size(font_size)
+                .text_color(label_color)
+                .border(border)
+                .border_color(border_color)
+                .placeholder("Replace")
+                .parent(idx)
+                .set(replacement_idx, &mut ui)
+            {
+                match event {
+                    widget::text_box::Event::Update(string) =>
+                        events.push(Event::Replacement(string)),
+                    widget::text_box::Event::Enter => events.push(Event::Replace),
+                    _ => (),
+                }
+            }
+
+            let replace_idx = state.replace_idx.get(&mut ui);
+            for _click in widget::Button::new()
+                .wh(buttons[0].dim())
+                .xy(buttons[0].xy())
+                .label("Replace")
+                .label_color(label_color)
+                .label_font_size(font_size)
+                .border(border)
+                .border_color(border_color)
+                .parent(idx)
+                .set(replace_idx, &mut ui)
+            {
+                events.push(Event::Replace);
+            }
+
+            let replace_all_idx = state.replace_all_idx.get(&mut ui);
+            for _click in widget::Button::new()
+                .wh(buttons[1].dim())
+                .xy(buttons[1].xy())
+                .label("All")
+                .label_color(label_color)
+                .label_font_size(font_size)
+                .border(border)
+                .border_color(border_color)
+                .parent(idx)
+                .set(replace_all_idx, &mut ui)
+            {
+                events.push(Event::ReplaceAll);
+            }
+        }
+
+        events
+    }
+
+}
+
+
+impl<'a> Colorable for FindBar<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+impl<'a> Borderable for FindBar<'a> {
+    builder_methods!{
+        border { style.border = Some(Scalar) }
+        border_color { style.border_color = Some(Color) }
+    }
+}
@@ -255,7 +255,8 @@ impl Widget for List {
                 let num_top_hidden_items = hidden_range_length / item_h;
                 let num_visible_items = (rect.h() / item_h + 1.0).ceil() as usize;
 
-                let first_visible_item_idx = num_top_hidden_items.floor() as usize;
+                // The scroll offset may be carried beyond the first item by a fling.
+                let first_visible_item_idx = num_top_hidden_items.floor().max(0.0) as usize;
                 let first_visible_item_margin = first_visible_item_idx as Scalar * item_h;
                 let end_of_visible_idx_range =
                     std::cmp::min(first_visible_item_idx + num_visible_items, num_items);
@@ -3,7 +3,9 @@
 //! This module contains items related to the implementation of the `Widget` trait. It also
 //! re-exports all widgets (and their modules) that are provided by conrod.
 
+use event;
 use graph::{self, NodeIndex};
+use input;
 use position::{Align, Depth, Dimension, Dimensions, Padding, Position, Positionable, Rect, Sizeable};
 use std;
 use text::font;
@@ -26,10 +28,12 @@ pub use self::primitive::text::{self, Text};
 pub use self::bordered_rectangle::BorderedRectangle;
 pub use self::button::Button;
 pub use self::canvas::Canvas;
+pub use self::code_editor::CodeEditor;
 pub use self::drop_down_list::DropDownList;
 pub use self::list_select::ListSelect;
 pub use self::envelope_editor::EnvelopeEditor;
 pub use self::file_navigator::FileNavigator;
+pub use self::find_bar::FindBar;
 pub use self::list::List;
 pub use self::matrix::Matrix;
 pub use self::number_dialer::NumberDialer;
@@ -42,6 +46,7 @@ pub use self::text_box::TextBox;
 pub use self::text_edit::TextEdit;
 pub use self::title_bar::TitleBar;
 pub use self::toggle::Toggle;
+pub use self::tooltip::Tooltip;
 pub use self::xy_pad::XYPad;
 
 
@@ -60,10 +65,12 @@ pub mod primitive;
 // Widget modules.
 pub mod button;
 pub mod canvas;
+pub mod code_editor;
 pub mod drop_down_list;
 pub mod list_select;
 pub mod envelope_editor;
 pub mod file_navigator;
+pub mod find_bar;
 pub mod bordered_rectangle;
 pub mod list;
 pub mod matrix;
@@ -77,6 +84,7 @@ pub mod text_box;
 pub mod text_edit;
 pub mod title_bar;
 pub mod toggle;
+pub mod tooltip;
 pub mod xy_pad;
 
 
@@ -240,6 +248,12 @@ pub struct CommonBuilder {
     /// default.
     /// - Any **Graphic** child of *b* will be considered as a **Graphic** child of *a*.
     pub maybe_graphics_for: Option<Index>,
+    /// The position of the **Widget** within the keyboard focus order if it is focusable.
+    ///
+    /// See the **Widget::tab_index** method for details.
+    pub maybe_tab_index: Option<u32>,
+    /// Text briefly describing the **Widget**, displayed within a **Tooltip** while hovered.
+    pub maybe_tooltip: Option<&'static str>,
 }
 
 /// Styling and positioning data that is common between all widget types.
@@ -329,6 +343,10 @@ pub struct PreUpdateCache {
     /// Whether or not the **Widget** has been instantiated as a graphical element for some other
     /// widget.
     pub maybe_graphics_for: Option<Index>,
+    /// The position of the **Widget** within the keyboard focus order if it is focusable.
+    pub maybe_tab_index: Option<u32>,
+    /// Text briefly describing the **Widget**, displayed within a **Tooltip** while hovered.
+    pub maybe_tooltip: Option<&'static str>,
 }
 
 // **Widget** data to be cached after the **Widget::update** call in the **widget::set_widget**
@@ -681,6 +699,32 @@ pub trait Widget: Sized {
         self
     }
 
+    /// Makes the widget focusable, giving it the given position within the keyboard focus order.
+    ///
+    /// Pressing `Tab` moves keyboard capture to the next focusable widget and `Shift+Tab` moves it
+    /// to the previous one. Widgets are visited in order of ascending tab index, while widgets that
+    /// share a tab index are visited in the order in which they were instantiated.
+    ///
+    /// While a focusable widget (or one of its children) captures the keyboard, a focus ring
+    /// styled by the `Theme`'s `focus_ring_color` and `focus_ring_width` is drawn around it.
+    fn tab_index(mut self, index: u32) -> Self {
+        self.common_mut().maybe_tab_index = Some(index);
+        self
+    }
+
+    /// Text briefly describing the widget.
+    ///
+    /// Once the mouse has rested over the widget for the `Theme`'s `hover_delay`, the text is
+    /// displayed within a **Tooltip** near the mouse. The **Tooltip** is hidden once the mouse
+    /// leaves the widget or any mouse button is pressed.
+    ///
+    /// The text is required to be `'static` so that the **CommonBuilder** remains `Copy`. In order
+    /// to display text produced at runtime, use `UiCell::set_tooltip`.
+    fn tooltip(mut self, text: &'static str) -> Self {
+        self.common_mut().maybe_tooltip = Some(text);
+        self
+    }
+
     /// Set whether or not the widget is floating (the default is `false`).
     /// A typical example of a floating widget would be a pop-up or alert window.
     ///
@@ -976,19 +1020,48 @@ fn set_widget<'a, 'b, W>(widget: W, idx: Index, ui: &'a mut UiCell<'b>) -> W::Ev
         scroll::State::update(ui, idx, &prev_kid_area, maybe_prev, 0.0)
     });
 
+    // While a finger pans the content of the widget, its scrolls drag the offset directly so that
+    // the content may be pulled beyond its edges.
+    let is_panned = ui.global_input.touch.values().any(|touch| touch.panned_widget == Some(idx));
+    let animation = ui.theme.scroll_animation;
+
     for scroll in ui.widget_input(idx).scrolls() {
 
         if widget.common().maybe_x_scroll.is_some() {
-            maybe_x_scroll_state =
-                Some(scroll::State::update(ui, idx, &prev_kid_area, maybe_x_scroll_state, scroll.x))
+            maybe_x_scroll_state = Some(if is_panned {
+                scroll::State::update(ui, idx, &prev_kid_area, maybe_x_scroll_state, 0.0)
+                    .pan(scroll.x, &animation)
+            } else {
+                scroll::State::update(ui, idx, &prev_kid_area, maybe_x_scroll_state, scroll.x)
+            });
         }
 
         if widget.common().maybe_y_scroll.is_some() {
-            maybe_y_scroll_state =
-                Some(scroll::State::update(ui, idx, &prev_kid_area, maybe_y_scroll_state, scroll.y))
+            maybe_y_scroll_state = Some(if is_panned {
+                scroll::State::update(ui, idx, &prev_kid_area, maybe_y_scroll_state, 0.0)
+                    .pan(scroll.y, &animation)
+            } else {
+                scroll::State::update(ui, idx, &prev_kid_area, maybe_y_scroll_state, scroll.y)
+            });
         }
     }
 
+    // Carry any flings onwards and step the scroll animations by the time elapsed since the last
+    // call to `Ui::set_widgets`.
+    //
+    // The offset is held wherever the panning finger leaves it until the finger is released.
+    for fling in ui.widget_input(idx).flings() {
+        maybe_x_scroll_state = maybe_x_scroll_state.map(|state| state.fling(fling.velocity[0]));
+        maybe_y_scroll_state = maybe_y_scroll_state.map(|state| state.fling(fling.velocity[1]));
+    }
+    if !is_panned {
+        let animation_secs = ui::animation_secs(ui);
+        maybe_x_scroll_state =
+            maybe_x_scroll_state.map(|state| state.step(animation_secs, &animation));
+        maybe_y_scroll_state =
+            maybe_y_scroll_state.map(|state| state.step(animation_secs, &animation));
+    }
+
     // Determine whether or not this is the first time set has been called.
     // We'll use this to determine whether or not we need to draw for the first time.
     let is_first_set = maybe_prev_common.is_none();
@@ -1029,6 +1102,8 @@ fn set_widget<'a, 'b, W>(widget: W, idx: Index, ui: &'a mut UiCell<'b>) -> W::Ev
             maybe_y_scroll_state: maybe_y_scroll_state,
             maybe_x_scroll_state: maybe_x_scroll_state,
             maybe_graphics_for: widget.common().maybe_graphics_for,
+            maybe_tab_index: widget.common().maybe_tab_index,
+            maybe_tooltip: widget.common().maybe_tooltip,
         });
     }
 
@@ -1170,6 +1245,8 @@ impl CommonBuilder {
             maybe_parent_idx: MaybeParent::Unspecified,
             place_on_kid_area: true,
             maybe_graphics_for: None,
+            maybe_tab_index: None,
+            maybe_tooltip: None,
             is_floating: false,
             maybe_x_scroll: None,
             maybe_y_scroll: None,
@@ -1241,6 +1318,44 @@ fn infer_position_from_other_position(other_pos: Position, dir_align: Align) ->
     }
 }
 
+// Whether or not the given event is one that activates a `Button` or `Toggle`.
+//
+// This includes the `Press`, `Release` and `Click` of the left mouse button along with the
+// `Press` and `Release` of the `Space` and `Return` keys.
+fn is_activation_event(event: &event::Widget) -> bool {
+    let is_activation_button = |button: &event::Button| match *button {
+        event::Button::Mouse(input::MouseButton::Left, _) |
+        event::Button::Keyboard(input::Key::Space) |
+        event::Button::Keyboard(input::Key::Return) => true,
+        _ => false,
+    };
+    match *event {
+        event::Widget::Press(ref press) => is_activation_button(&press.button),
+        event::Widget::Release(ref release) => is_activation_button(&release.button),
+        event::Widget::Click(ref click) => click.button == input::MouseButton::Left,
+        _ => false,
+    }
+}
+
+// Whether or not the given event is one that a `Slider`, `RangeSlider` or `XYPad` acts upon.
+//
+// This includes the `Press`, `Release`, `Click` and `Drag` of the left mouse button.
+fn is_slide_event(event: &event::Widget) -> bool {
+    match *event {
+        event::Widget::Press(ref press) => match press.button {
+            event::Button::Mouse(input::MouseButton::Left, _) => true,
+            _ => false,
+        },
+        event::Widget::Release(ref release) => match release.button {
+            event::Button::Mouse(input::MouseButton::Left, _) => true,
+            _ => false,
+        },
+        event::Widget::Click(ref click) => click.button == input::MouseButton::Left,
+        event::Widget::Drag(ref drag) => drag.button == input::MouseButton::Left,
+        _ => false,
+    }
+}
+
 
 impl<W> Sizeable for W
     where W: Widget,
@@ -59,6 +59,9 @@ widget_style!{
 pub struct State {
     /// The index of the value that is currently pressed.
     pressed_value_idx: Option<usize>,
+    /// The part of the controller adjustments that is too fine to be displayed at the given
+    /// precision, carried over to the following adjustments.
+    adjustment_remainder: Scalar,
     rectangle_idx: widget::IndexSlot,
     label_idx: widget::IndexSlot,
     glyph_slot_indices: Vec<GlyphSlot>,
@@ -160,6 +163,7 @@ impl<'a, T> Widget for NumberDialer<'a, T>
     fn init_state(&self) -> Self::State {
         State {
             pressed_value_idx: None,
+            adjustment_remainder: 0.0,
             rectangle_idx: widget::IndexSlot::new(),
             label_idx: widget::IndexSlot::new(),
             glyph_slot_indices: Vec::new(),
@@ -232,6 +236,7 @@ impl<'a, T> Widget for NumberDialer<'a, T>
         let value_under_mouse = ui.widget_input(idx).mouse()
             .and_then(|m| value_under_rel_xy(m.rel_xy()));
         let mut pressed_value_idx = state.pressed_value_idx;
+        let mut adjustment_remainder = state.adjustment_remainder;
         let mut new_value = value;
 
         // Check for the following events:
@@ -297,6 +302,25 @@ impl<'a, T> Widget for NumberDialer<'a, T>
                     }
                 },
 
+                // Adjust the value with any controller stick pushed while the dialer has keyboard
+                // focus.
+                //
+                // The value is rounded to the displayed precision.
+                event::Widget::Adjust(adjust) => {
+                    let val_f: f64 = NumCast::from(new_value).unwrap();
+                    let min_f: f64 = NumCast::from(min).unwrap();
+                    let max_f: f64 = NumCast::from(max).unwrap();
+                    let step = 10.0f64.powi(-(precision as i32));
+                    let target = val_f + adjustment_remainder + adjust.amount[1] * (max_f - min_f);
+                    let new_val_f = clamp((target / step).round() * step, min_f, max_f);
+                    adjustment_remainder = if min_f < target && target < max_f {
+                        target - new_val_f
+                    } else {
+                        0.0
+                    };
+                    new_value = NumCast::from(new_val_f).unwrap();
+                },
+
                 _ => (),
             }
         }
@@ -308,6 +332,10 @@ impl<'a, T> Widget for NumberDialer<'a, T>
             state.update(|state| state.pressed_value_idx = pressed_value_idx);
         }
 
+        if state.adjustment_remainder != adjustment_remainder {
+            state.update(|state| state.adjustment_remainder = adjustment_remainder);
+        }
+
         // The **Rectangle** backdrop widget.
         let color = style.color(ui.theme());
         let border = style.border(ui.theme());
@@ -111,6 +111,12 @@ impl<'a> Text<'a> {
         self
     }
 
+    /// Draw the **Text** with the font at the given `Id`.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.font_id = Some(Some(font_id));
+        self
+    }
+
     /// Build the **Text** with the given **Style**.
     pub fn with_style(mut self, style: Style) -> Self {
         self.style = style;
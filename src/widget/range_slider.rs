@@ -13,6 +13,7 @@ use {
     Scalar,
     Widget,
 };
+use cursor::MouseCursor;
 use num::{Float, NumCast, ToPrimitive};
 use utils;
 use widget;
@@ -48,6 +49,9 @@ widget_style!{
 #[derive(Clone, Debug, PartialEq)]
 pub struct State {
     drag: Option<Drag>,
+    /// The `start` and `end` values at the time the current drag began, restored if it is
+    /// cancelled.
+    pre_drag: Option<(Scalar, Scalar)>,
     border_idx: widget::IndexSlot,
     slider_idx: widget::IndexSlot,
     label_idx: widget::IndexSlot,
@@ -130,6 +134,7 @@ impl<'a, T> Widget for RangeSlider<'a, T>
     fn init_state(&self) -> Self::State {
         State {
             drag: None,
+            pre_drag: None,
             border_idx: widget::IndexSlot::new(),
             slider_idx: widget::IndexSlot::new(),
             label_idx: widget::IndexSlot::new(),
@@ -163,6 +168,7 @@ impl<'a, T> Widget for RangeSlider<'a, T>
         let x_to_value = |x| utils::map_range(x, inner_rect.left(), inner_rect.right(), min, max);
 
         let mut maybe_drag = state.drag;
+        let mut pre_drag = state.pre_drag;
         let mut new_start = start;
         let mut new_end = utils::clamp(end, start, max);
         for widget_event in ui.widget_input(idx).events() {
@@ -182,6 +188,11 @@ impl<'a, T> Widget for RangeSlider<'a, T>
                     };
                     let abs_press_xy = utils::vec2_add(inner_rect.xy(), press_xy);
                     if inner_rect.is_over(abs_press_xy) {
+                        if pre_drag.is_none() {
+                            let start = new_start.to_f64().unwrap();
+                            let end = new_end.to_f64().unwrap();
+                            pre_drag = Some((start, end));
+                        }
                         let start_x = value_to_x(new_start);
                         let end_x = value_to_x(new_end);
                         let length_x = end_x - start_x;
@@ -253,7 +264,20 @@ impl<'a, T> Widget for RangeSlider<'a, T>
                 event::Widget::Release(release) => {
                     if let event::Button::Mouse(input::MouseButton::Left, _) = release.button {
                         maybe_drag = None;
+                        pre_drag = None;
+                    }
+                },
+
+                // Restore the values from before the drag began.
+                event::Widget::DragCancelled(cancelled) => {
+                    if cancelled.button != input::MouseButton::Left {
+                        continue;
+                    }
+                    if let Some((start, end)) = pre_drag.take() {
+                        new_start = NumCast::from(start).unwrap();
+                        new_end = NumCast::from(end).unwrap();
                     }
+                    maybe_drag = None;
                 },
 
                 _ => (),
@@ -261,6 +285,14 @@ impl<'a, T> Widget for RangeSlider<'a, T>
 
         }
 
+        // Stop the mouse events that move the range from bubbling up to the slider's parent.
+        let handled_events: Vec<_> = ui.widget_input(idx).events()
+            .filter(|event| widget::is_slide_event(event))
+            .collect();
+        for event in handled_events {
+            ui.set_event_handled(idx, event);
+        }
+
         // If the value has just changed, or if the slider has been clicked/released, produce an
         // event.
         let event = Event {
@@ -268,8 +300,37 @@ impl<'a, T> Widget for RangeSlider<'a, T>
             end: if end != new_end { Some(new_end) } else { None },
         };
 
-        if maybe_drag != state.drag {
-            state.update(|state| state.drag = maybe_drag);
+        if maybe_drag != state.drag || pre_drag != state.pre_drag {
+            state.update(|state| {
+                state.drag = maybe_drag;
+                state.pre_drag = pre_drag;
+            });
+        }
+
+        // Show a resize cursor while over or dragging either edge and a grabbing hand while over or
+        // dragging the range itself.
+        let maybe_cursor = match maybe_drag {
+            Some(Drag::Edge(_)) => Some(MouseCursor::ResizeHorizontal),
+            Some(Drag::Handle) => Some(MouseCursor::Grab),
+            None => ui.widget_input(idx).mouse().and_then(|mouse| {
+                let abs_xy = mouse.abs_xy();
+                let start_x = value_to_x(new_start);
+                let end_x = value_to_x(new_end);
+                let grab_edge_threshold = (end_x - start_x) / 10.0;
+                let handle_rect = Rect { x: Range::new(start_x, end_x), y: inner_rect.y };
+                let is_over_edge = (abs_xy[0] - start_x).abs() < grab_edge_threshold
+                    || (end_x - abs_xy[0]).abs() < grab_edge_threshold;
+                if !handle_rect.is_over(abs_xy) {
+                    None
+                } else if is_over_edge {
+                    Some(MouseCursor::ResizeHorizontal)
+                } else {
+                    Some(MouseCursor::Grab)
+                }
+            }),
+        };
+        if let Some(cursor) = maybe_cursor {
+            ui.set_mouse_cursor(idx, cursor);
         }
 
         // The **Rectangle** for the border.
@@ -29,10 +29,62 @@ pub struct State<A> {
     pub scrollable_range_len: Scalar,
     /// Whether or not the this axis is currently scrolling.
     pub is_scrolling: bool,
+    /// The offset towards which the `offset` is animating.
+    ///
+    /// This always lies within the `offset_bounds`, whereas the `offset` may be pulled beyond them
+    /// by a pan or a fling before springing back.
+    pub target_offset: Scalar,
+    /// The velocity of the current fling in points per second, or `0.0` if there is none.
+    pub velocity: Scalar,
+    /// The offset from which the current animation towards the `target_offset` began.
+    animation_start_offset: Scalar,
+    /// The time elapsed since the current animation towards the `target_offset` began.
+    animation_secs: Scalar,
+    /// Whether or not the `scrollable_range` is longer than the `kid_area`.
+    is_scrollable: bool,
     /// The axis type used to instantiate this state.
     axis: PhantomData<A>,
 }
 
+/// The curve along which an animated scroll approaches its target offset.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Easing {
+    /// Move towards the target at a constant speed.
+    Linear,
+    /// Start quickly and decelerate towards the target.
+    QuadraticOut,
+    /// Start quickly and decelerate towards the target more sharply than `QuadraticOut`.
+    CubicOut,
+    /// Accelerate away from the start and decelerate towards the target.
+    QuadraticInOut,
+}
+
+/// Describes how scrolling widgets animate towards their target offset, how flings lose their
+/// momentum and how far flings may overscroll the edges of the scrollable range.
+///
+/// All animation is stepped by the time reported by the `Render` events given to the `Ui` via
+/// `Ui::handle_event`. As an application that does not pass `Render` events would never see its
+/// widgets scroll, the `Theme` defaults to `Animation::instant()`. Animation may be enabled by
+/// setting `theme.scroll_animation` to `Animation::default()` or a custom `Animation`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Animation {
+    /// The time taken for a scroll to reach its target offset in seconds.
+    ///
+    /// A duration of `0.0` applies each scroll instantly.
+    pub duration_secs: Scalar,
+    /// The curve along which the offset approaches its target.
+    pub easing: Easing,
+    /// The fraction of a fling's velocity that is lost each second.
+    pub friction: Scalar,
+    /// Flings slower than this many points per second come to rest.
+    pub min_velocity: Scalar,
+    /// The furthest distance that a pan or a fling may carry the offset beyond the edge of the
+    /// scrollable range before springing back.
+    ///
+    /// A distance of `0.0` stops pans and flings at the edge.
+    pub overscroll: Scalar,
+}
+
 /// Methods for distinguishing behaviour between both scroll axes at compile-time.
 pub trait Axis {
     /// The range of the given `Rect` that is parallel with this `Axis`.
@@ -73,6 +125,55 @@ impl Scroll {
 }
 
 
+impl Easing {
+    /// The progress along the curve at the given fraction `t` of the animation's duration, where
+    /// `t` ranges from `0.0` to `1.0`.
+    pub fn ease(self, t: Scalar) -> Scalar {
+        match self {
+            Easing::Linear => t,
+            Easing::QuadraticOut => t * (2.0 - t),
+            Easing::CubicOut => {
+                let u = t - 1.0;
+                u * u * u + 1.0
+            },
+            Easing::QuadraticInOut => if t < 0.5 {
+                2.0 * t * t
+            } else {
+                -1.0 + (4.0 - 2.0 * t) * t
+            },
+        }
+    }
+}
+
+
+impl Animation {
+
+    /// Applies each scroll instantly, stops flings immediately and never overscrolls.
+    pub fn instant() -> Self {
+        Animation {
+            duration_secs: 0.0,
+            easing: Easing::Linear,
+            friction: 1.0,
+            min_velocity: ::std::f64::INFINITY,
+            overscroll: 0.0,
+        }
+    }
+
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Animation {
+            duration_secs: 0.15,
+            easing: Easing::CubicOut,
+            friction: 0.95,
+            min_velocity: 10.0,
+            overscroll: 60.0,
+        }
+    }
+}
+
+
 impl<A> State<A>
     where A: Axis
 {
@@ -160,10 +261,13 @@ impl<A> State<A>
                   additional_offset: Scalar) -> Self
     {
 
-        // Retrieve the *current* scroll offset.
+        // Retrieve the *current* scroll offset along with the offset towards which it animates.
         let current_offset = maybe_prev_scroll_state.as_ref()
             .map(|state| state.offset)
             .unwrap_or(0.0);
+        let current_target_offset = maybe_prev_scroll_state.as_ref()
+            .map(|state| state.target_offset)
+            .unwrap_or(current_offset);
 
         // Padding for the range.
         let padding = A::padding_range(kid_area.pad);
@@ -194,30 +298,176 @@ impl<A> State<A>
         // The range is only scrollable if it is longer than the padded kid_area_range.
         let is_scrollable = scrollable_range.len() > kid_area_range.len();
 
-        // If the range is scrollable, calculate the new offset by adding the `additional_offset`.
+        // If the range is scrollable, calculate the new target offset by adding the
+        // `additional_offset`.
         //
         // The `additional_offset` is given via a `Scroll` event.
-        let new_offset_unbounded =
-            if is_scrollable { current_offset + additional_offset }
-            else             { current_offset };
+        let new_target_offset_unbounded =
+            if is_scrollable { current_target_offset + additional_offset }
+            else             { current_target_offset };
 
-        // Clamp the new offset to ensure it does not exceed the `offset_bounds`.
-        let new_offset = {
+        // Clamp the new target offset to ensure it does not exceed the `offset_bounds`.
+        let new_target_offset = {
             // If there was some previous scroll state, we must also ensure that our new offset does
             // exceed its `offset_bounds` either. We do this in order to avoid causing jitter when
             // scrolling towards either end of the Range.
             let new_offset = maybe_prev_scroll_state.as_ref()
-                .map(|prev| prev.offset_bounds.clamp_value(new_offset_unbounded))
-                .unwrap_or(new_offset_unbounded);
+                .map(|prev| prev.offset_bounds.clamp_value(new_target_offset_unbounded))
+                .unwrap_or(new_target_offset_unbounded);
             offset_bounds.clamp_value(new_offset)
         };
 
+        // A new scroll interrupts any fling and restarts the animation from the current offset.
+        let is_scrolling = additional_offset != 0.0;
+        let (velocity, animation_start_offset, animation_secs) = match maybe_prev_scroll_state {
+            Some(prev) if !is_scrolling =>
+                (prev.velocity, prev.animation_start_offset, prev.animation_secs),
+            _ => (0.0, current_offset, 0.0),
+        };
+
+        // The first state begins at rest upon its target.
+        let offset = match maybe_prev_scroll_state {
+            Some(_) => current_offset,
+            None => new_target_offset,
+        };
+
         State {
-            offset: new_offset,
+            offset: offset,
             offset_bounds: offset_bounds,
             scrollable_range_len: scrollable_range.len(),
             axis: PhantomData,
-            is_scrolling: additional_offset != 0.0,
+            is_scrolling: is_scrolling,
+            target_offset: new_target_offset,
+            velocity: velocity,
+            animation_start_offset: animation_start_offset,
+            animation_secs: animation_secs,
+            is_scrollable: is_scrollable,
+        }
+    }
+
+    /// Begin a fling with the given velocity in points per second, replacing any fling that is
+    /// already in progress.
+    ///
+    /// The fling carries the offset onwards until its velocity is lost to the `friction` of the
+    /// `Animation` given to `State::step`.
+    pub fn fling(self, velocity: Scalar) -> Self {
+        if !self.is_scrollable {
+            return self;
+        }
+        State {
+            velocity: velocity,
+            is_scrolling: self.is_scrolling || velocity != 0.0,
+            ..self
+        }
+    }
+
+    /// Drag the offset by the given distance, as when a finger pans the scrollable content.
+    ///
+    /// Unlike a `Scroll`, which only moves the `target_offset`, a pan moves the `offset` directly
+    /// and may pull it beyond the `offset_bounds`, meeting increasing resistance as it approaches
+    /// the `Animation`'s `overscroll` distance. The `target_offset` remains within the bounds, so
+    /// that once the pan is released, `State::step` springs the offset back.
+    pub fn pan(self, additional_offset: Scalar, animation: &Animation) -> Self {
+        if !self.is_scrollable {
+            return self;
+        }
+
+        // Undo the resistance applied to the current overscroll, so that the resistance applied
+        // to the new overscroll depends only on the total distance panned beyond the bounds.
+        let max_overscroll = animation.overscroll;
+        let bounded_offset = self.offset_bounds.clamp_value(self.offset);
+        let overscroll = self.offset - bounded_offset;
+        let unresisted_overscroll = if overscroll != 0.0 && max_overscroll > 0.0 {
+            let remaining = (max_overscroll - overscroll.abs()).max(::std::f64::EPSILON);
+            max_overscroll * overscroll / remaining
+        } else {
+            0.0
+        };
+
+        let unresisted_offset = bounded_offset + unresisted_overscroll + additional_offset;
+        let target_offset = self.offset_bounds.clamp_value(unresisted_offset);
+        let unresisted_overscroll = unresisted_offset - target_offset;
+        let overscroll = if max_overscroll > 0.0 {
+            max_overscroll * unresisted_overscroll / (max_overscroll + unresisted_overscroll.abs())
+        } else {
+            0.0
+        };
+        let offset = target_offset + overscroll;
+
+        State {
+            offset: offset,
+            target_offset: target_offset,
+            velocity: 0.0,
+            animation_start_offset: offset,
+            animation_secs: 0.0,
+            is_scrolling: self.is_scrolling || offset != self.offset,
+            ..self
+        }
+    }
+
+    /// Step the `offset` towards the `target_offset` and carry any fling onwards by the given
+    /// number of seconds.
+    ///
+    /// A fling that carries the offset beyond the `offset_bounds` is rapidly slowed and, once it
+    /// comes to rest, springs back to the bound along the `Animation`'s easing curve. Likewise, an
+    /// offset left beyond the bounds by a released pan springs back.
+    pub fn step(self, secs: Scalar, animation: &Animation) -> Self {
+        let State { mut offset, mut target_offset, mut velocity,
+                    mut animation_start_offset, mut animation_secs, .. } = self;
+
+        if velocity != 0.0 {
+            offset += velocity * secs;
+            velocity *= (1.0 - animation.friction).max(0.0).powf(secs);
+
+            // Slow the fling the further it carries the offset beyond the bounds.
+            let bounded_offset = self.offset_bounds.clamp_value(offset);
+            let overscroll = offset - bounded_offset;
+            if overscroll != 0.0 {
+                let fraction = if animation.overscroll > 0.0 {
+                    overscroll.abs() / animation.overscroll
+                } else {
+                    1.0
+                };
+                if fraction >= 1.0 {
+                    offset = bounded_offset + overscroll.signum() * animation.overscroll;
+                    velocity = 0.0;
+                } else {
+                    velocity *= 1.0 - fraction;
+                }
+            }
+
+            if velocity.abs() < animation.min_velocity {
+                velocity = 0.0;
+            }
+
+            // Once the fling comes to rest, animate towards the nearest offset within the bounds.
+            target_offset = bounded_offset;
+            animation_start_offset = offset;
+            animation_secs = 0.0;
+
+        } else if offset != target_offset {
+            animation_secs += secs;
+            let t = if animation.duration_secs > 0.0 {
+                (animation_secs / animation.duration_secs).min(1.0)
+            } else {
+                1.0
+            };
+            let distance = target_offset - animation_start_offset;
+            offset = if t < 1.0 {
+                animation_start_offset + distance * animation.easing.ease(t)
+            } else {
+                target_offset
+            };
+        }
+
+        State {
+            offset: offset,
+            target_offset: target_offset,
+            velocity: velocity,
+            animation_start_offset: animation_start_offset,
+            animation_secs: animation_secs,
+            is_scrolling: self.is_scrolling || offset != self.offset,
+            ..self
         }
     }
 
@@ -272,3 +522,91 @@ impl Axis for Y {
     }
 
 }
+
+
+#[test]
+fn flings_should_overscroll_and_spring_back_within_the_bounds() {
+    let animation = Animation {
+        duration_secs: 1.0,
+        easing: Easing::Linear,
+        friction: 0.5,
+        min_velocity: 10.0,
+        overscroll: 50.0,
+    };
+    let state = StateY {
+        offset: 0.0,
+        offset_bounds: Range::new(-100.0, 0.0),
+        scrollable_range_len: 300.0,
+        is_scrolling: false,
+        target_offset: -100.0,
+        velocity: 0.0,
+        animation_start_offset: 0.0,
+        animation_secs: 0.0,
+        is_scrollable: true,
+        axis: PhantomData,
+    };
+
+    // Scrolls animate towards their target along the easing curve.
+    let state = state.step(0.5, &animation);
+    assert_eq!(state.offset, -50.0);
+    let state = state.step(0.5, &animation);
+    assert_eq!(state.offset, -100.0);
+
+    // A fast fling is carried no further than the overscroll distance beyond the bounds.
+    let state = state.fling(-1000.0).step(0.5, &animation);
+    assert_eq!(state.offset, -150.0);
+    assert_eq!(state.velocity, 0.0);
+    assert_eq!(state.target_offset, -100.0);
+
+    // Once at rest, the offset springs back to the bound.
+    let state = state.step(1.0, &animation);
+    assert_eq!(state.offset, -100.0);
+}
+
+#[test]
+fn pans_should_overscroll_with_resistance_and_spring_back_once_released() {
+    let animation = Animation {
+        duration_secs: 1.0,
+        easing: Easing::Linear,
+        friction: 0.5,
+        min_velocity: 10.0,
+        overscroll: 50.0,
+    };
+    let state = StateY {
+        offset: -90.0,
+        offset_bounds: Range::new(-100.0, 0.0),
+        scrollable_range_len: 300.0,
+        is_scrolling: false,
+        target_offset: -90.0,
+        velocity: 0.0,
+        animation_start_offset: -90.0,
+        animation_secs: 0.0,
+        is_scrollable: true,
+        axis: PhantomData,
+    };
+
+    // Within the bounds, a pan moves the offset the full distance.
+    let state = state.pan(-10.0, &animation);
+    assert_eq!(state.offset, -100.0);
+
+    // Beyond the bounds, each further pan moves the offset a shorter distance.
+    let state = state.pan(-50.0, &animation);
+    assert_eq!(state.offset, -125.0);
+    assert_eq!(state.target_offset, -100.0);
+    let state = state.pan(-50.0, &animation);
+    assert!(state.offset < -125.0 && state.offset > -150.0);
+
+    // Panning back towards the bounds retraces the same path.
+    let state = state.pan(50.0, &animation);
+    assert!((state.offset - -125.0).abs() < 1e-9);
+
+    // Once released, the offset springs back to the bound.
+    let state = state.step(0.5, &animation);
+    assert!((state.offset - -112.5).abs() < 1e-9);
+    let state = state.step(0.5, &animation);
+    assert_eq!(state.offset, -100.0);
+
+    // Without any overscroll distance, pans stop at the edge.
+    let state = state.pan(-50.0, &Animation::instant());
+    assert_eq!(state.offset, -100.0);
+}
@@ -197,7 +197,8 @@ impl<A> Widget for Scrollbar<A>
                 let pos_min = handle_range.align_start_of(track_range).middle();
                 let pos_max = handle_range.align_end_of(track_range).middle();
                 let pos_bounds = Range::new(pos_min, pos_max);
-                offset_bounds.map_value_to(offset, &pos_bounds)
+                // The offset may be carried beyond its bounds by a fling.
+                offset_bounds.map_value_to(offset_bounds.clamp_value(offset), &pos_bounds)
             };
             let range = Range::from_pos_and_len(pos, len);
             A::handle_rect(perpendicular_track_range, range)
@@ -13,6 +13,7 @@ use {
     Scalar,
     Widget,
 };
+use cursor::MouseCursor;
 use num::{Float, NumCast, ToPrimitive};
 use widget;
 
@@ -130,7 +131,7 @@ impl<'a, T> Widget for Slider<'a, T>
 
     /// Update the state of the Slider.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
-        use utils::{clamp, map_range, value_from_perc};
+        use utils::{clamp, map_range, percentage, value_from_perc};
 
         let widget::UpdateArgs { idx, state, rect, style, mut ui, .. } = args;
         let Slider { value, min, max, skew, maybe_label, .. } = self;
@@ -166,6 +167,34 @@ impl<'a, T> Widget for Slider<'a, T>
             value
         };
 
+        // Adjust the value with any controller stick pushed while the slider has keyboard focus.
+        //
+        // The adjustment is applied along the slider's length prior to skewing, as with the mouse.
+        let new_value = ui.widget_input(idx).adjustments().fold(new_value, |value, adjust| {
+            let amount = if is_horizontal { adjust.amount[0] } else { adjust.amount[1] };
+            let perc = clamp(percentage(value, min, max) as f64, 0.0, 1.0).powf(1.0 / skew as f64);
+            let perc = clamp(perc + amount, 0.0, 1.0);
+            let skewed_perc = perc.powf(skew as f64);
+            value_from_perc(skewed_perc as f32, min, max)
+        });
+
+        // Stop the mouse events that slide the value from bubbling up to the slider's parent.
+        let handled_events: Vec<_> = ui.widget_input(idx).events()
+            .filter(|event| widget::is_slide_event(event))
+            .collect();
+        for event in handled_events {
+            ui.set_event_handled(idx, event);
+        }
+
+        // Pressing anywhere moves the edge of the slider to the mouse, so the whole widget behaves
+        // as a handle for resizing the slidable rectangle.
+        let cursor = if is_horizontal {
+            MouseCursor::ResizeHorizontal
+        } else {
+            MouseCursor::ResizeVertical
+        };
+        ui.set_mouse_cursor(idx, cursor);
+
         // The **Rectangle** for the border.
         let border_idx = state.border_idx.get(&mut ui);
 
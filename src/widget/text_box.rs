@@ -1,6 +1,7 @@
 //! A widget for displaying and mutating a one-line field of text.
 
 use {
+    color,
     Align,
     Color,
     Colorable,
@@ -15,16 +16,30 @@ use {
 };
 use event;
 use input;
+use text;
 use widget;
 
 /// A widget for displaying and mutating a small, one-line field of text, given by the user in the
 /// form of a `String`.
 ///
 /// It's reaction is triggered upon pressing of the `Enter`/`Return` key.
+///
+/// Copy, cut and paste are handled by the inner `TextEdit` via the `Ui`'s clipboard.
+///
+/// The text that may be entered can be restricted via a `Filter`, a custom `validator`, a
+/// `max_len` and an input `mask`.
 pub struct TextBox<'a> {
     common: widget::CommonBuilder,
     text: &'a str,
     style: Style,
+    maybe_key_bindings: Option<&'a widget::text_edit::KeyBindings>,
+    maybe_filter: Option<Filter<'a>>,
+    maybe_validator: Option<Box<Fn(&str) -> bool + 'a>>,
+    maybe_max_len: Option<usize>,
+    maybe_mask: Option<&'a str>,
+    maybe_password_char: Option<char>,
+    maybe_placeholder: Option<&'a str>,
+    flag_invalid: bool,
 }
 
 widget_style!{
@@ -47,6 +62,16 @@ widget_style!{
         - font_size: FontSize { theme.font_size_medium }
         /// The horizontal alignment of the text.
         - x_align: Align { Align::Start }
+        /// The color of the `BorderedRectangle`'s border while the text is invalid.
+        - invalid_border_color: Color { color::RED }
+        /// The color of the placeholder text.
+        ///
+        /// If `None`, the `text_color` is used with a reduced alpha.
+        - placeholder_color: Option<Color> { None }
+        /// The font used for the placeholder text.
+        ///
+        /// If `None`, the `TextEdit`'s font is used.
+        - placeholder_font_id: Option<text::font::Id> { None }
     }
 }
 
@@ -55,19 +80,134 @@ widget_style!{
 pub struct State {
     text_edit_idx: widget::IndexSlot,
     rectangle_idx: widget::IndexSlot,
+    /// Whether or not the text was valid when last checked.
+    maybe_is_valid: Option<bool>,
+}
+
+/// Built-in filters for the text that may be entered into a `TextBox`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Filter<'a> {
+    /// An integer with an optional leading sign.
+    Integer,
+    /// A floating point number within the optional `min` and `max` bounds (inclusive).
+    Float {
+        /// The minimum valid value.
+        min: Option<f64>,
+        /// The maximum valid value.
+        max: Option<f64>,
+    },
+    /// Hexadecimal digits.
+    Hex,
+    /// Only those characters within the given regex-like class, e.g. `[a-zA-Z0-9_]`.
+    ///
+    /// Ranges are given as `a-z`, a leading `^` negates the class and `\` escapes the following
+    /// character. The surrounding brackets are optional.
+    CharClass(&'a str),
 }
 
 impl<'a> TextBox<'a> {
 
     /// Construct a TextBox widget.
-    pub fn new(text: &'a mut String) -> Self {
+    pub fn new(text: &'a str) -> Self {
         TextBox {
             common: widget::CommonBuilder::new(),
             text: text,
             style: Style::new(),
+            maybe_key_bindings: None,
+            maybe_filter: None,
+            maybe_validator: None,
+            maybe_max_len: None,
+            maybe_mask: None,
+            maybe_password_char: None,
+            maybe_placeholder: None,
+            flag_invalid: false,
         }
     }
 
+    /// Restrict the text to that accepted by the given `Filter`.
+    ///
+    /// Empty text is never flagged as invalid by the `Filter`, though it is still checked by any
+    /// `validator`.
+    pub fn filter(mut self, filter: Filter<'a>) -> Self {
+        self.maybe_filter = Some(filter);
+        self
+    }
+
+    /// Validate the text with the given function, returning `true` if the text is valid.
+    ///
+    /// Unless `flag_invalid` is used, edits that produce invalid text are rejected, so the function
+    /// should accept any text that may become valid via further input.
+    pub fn validator<F>(mut self, validator: F) -> Self
+        where F: Fn(&str) -> bool + 'a,
+    {
+        self.maybe_validator = Some(Box::new(validator));
+        self
+    }
+
+    /// Reject edits that would cause the text to exceed the given number of characters.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.maybe_max_len = Some(max_len);
+        self
+    }
+
+    /// Format the text with the given input mask as it is entered, e.g. `##:##:##`.
+    ///
+    /// See `TextEdit::mask` for the mask syntax.
+    pub fn mask(mut self, mask: &'a str) -> Self {
+        self.maybe_mask = Some(mask);
+        self
+    }
+
+    /// Obscure the text for password entry, displaying each character as a `•`.
+    ///
+    /// See `TextEdit::password`.
+    pub fn password(self) -> Self {
+        self.password_char('•')
+    }
+
+    /// Obscure the text for password entry, displaying each character as the given `char`.
+    ///
+    /// See `TextEdit::password_char`.
+    pub fn password_char(mut self, password_char: char) -> Self {
+        self.maybe_password_char = Some(password_char);
+        self
+    }
+
+    /// Hint text that is displayed while the text is empty and the `TextBox` is not being edited.
+    pub fn placeholder(mut self, placeholder: &'a str) -> Self {
+        self.maybe_placeholder = Some(placeholder);
+        self
+    }
+
+    /// The color of the `placeholder` text.
+    ///
+    /// By default, the `text_color` is used with a reduced alpha.
+    pub fn placeholder_color(mut self, color: Color) -> Self {
+        self.style.placeholder_color = Some(Some(color));
+        self
+    }
+
+    /// The font used for the `placeholder` text.
+    pub fn placeholder_font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.placeholder_font_id = Some(Some(font_id));
+        self
+    }
+
+    /// Rather than rejecting edits that would produce invalid text, accept them and flag the
+    /// `TextBox` as invalid using the `invalid_border_color`.
+    ///
+    /// Edits exceeding the `max_len` are always rejected.
+    pub fn flag_invalid(mut self) -> Self {
+        self.flag_invalid = true;
+        self
+    }
+
+    /// Use the given `KeyBindings` for the inner `TextEdit`'s editing commands.
+    pub fn key_bindings(mut self, key_bindings: &'a widget::text_edit::KeyBindings) -> Self {
+        self.maybe_key_bindings = Some(key_bindings);
+        self
+    }
+
     /// Align the text to the left of its bounding **Rect**'s *x* axis range.
     pub fn align_text_left(self) -> Self {
         self.x_align_text(Align::Start)
@@ -88,6 +228,7 @@ impl<'a> TextBox<'a> {
         pub font_size { style.font_size = Some(FontSize) }
         pub x_align_text { style.x_align = Some(Align) }
         pub pad_text { style.text_padding = Some(Scalar) }
+        pub invalid_border_color { style.invalid_border_color = Some(Color) }
     }
 
 }
@@ -99,8 +240,82 @@ pub enum Event {
     Update(String),
     /// The `Return` or `Enter` key was pressed.
     Enter,
+    /// Whether or not the text is valid given the `Filter` and `validator`.
+    ///
+    /// Produced upon the first update and whenever the validity of the text changes. Empty text is
+    /// only invalid if rejected by the `validator`.
+    Validity(bool),
 }
 
+
+impl<'a> Filter<'a> {
+
+    /// Whether or not the given text may become valid via further input.
+    ///
+    /// This is used to reject edits. For example, `-` is not a valid `Integer` but is accepted as
+    /// it may be followed by digits.
+    pub fn accepts(&self, text: &str) -> bool {
+        match *self {
+            Filter::Integer => {
+                let digits = text.trim_left_matches(|c| c == '-' || c == '+');
+                text.len() - digits.len() <= 1 && digits.chars().all(|c| c.is_digit(10))
+            },
+            Filter::Float { .. } => {
+                let digits = text.trim_left_matches(|c| c == '-' || c == '+');
+                text.len() - digits.len() <= 1
+                    && digits.chars().filter(|&c| c == '.').count() <= 1
+                    && digits.chars().all(|c| c.is_digit(10) || c == '.')
+            },
+            Filter::Hex => text.chars().all(|c| c.is_digit(16)),
+            Filter::CharClass(class) => text.chars().all(|c| char_class_contains(class, c)),
+        }
+    }
+
+    /// Whether or not the given text is valid.
+    pub fn is_valid(&self, text: &str) -> bool {
+        match *self {
+            Filter::Integer => text.parse::<i64>().is_ok(),
+            Filter::Float { min, max } => match text.parse::<f64>() {
+                Ok(f) => min.map_or(true, |min| f >= min) && max.map_or(true, |max| f <= max),
+                Err(_) => false,
+            },
+            Filter::Hex => !text.is_empty() && self.accepts(text),
+            Filter::CharClass(_) => self.accepts(text),
+        }
+    }
+
+}
+
+/// Whether or not the given character is a member of the given regex-like character class.
+fn char_class_contains(class: &str, c: char) -> bool {
+    let class = if class.starts_with('[') && class.ends_with(']') && class.len() >= 2 {
+        &class[1..class.len() - 1]
+    } else {
+        class
+    };
+    let (negated, class) = if class.starts_with('^') { (true, &class[1..]) }
+                           else                      { (false, class) };
+
+    let mut chars = class.chars().peekable();
+    let mut contains = false;
+    while let Some(first) = chars.next() {
+        let first = if first == '\\' { chars.next().unwrap_or(first) } else { first };
+        let is_range = chars.peek() == Some(&'-') && chars.clone().nth(1).is_some();
+        if is_range {
+            chars.next();
+            let last = chars.next().unwrap();
+            let last = if last == '\\' { chars.next().unwrap_or(last) } else { last };
+            if first <= c && c <= last {
+                contains = true;
+            }
+        } else if first == c {
+            contains = true;
+        }
+    }
+    contains != negated
+}
+
+
 impl<'a> Widget for TextBox<'a> {
     type State = State;
     type Style = Style;
@@ -118,6 +333,7 @@ impl<'a> Widget for TextBox<'a> {
         State {
             text_edit_idx: widget::IndexSlot::new(),
             rectangle_idx: widget::IndexSlot::new(),
+            maybe_is_valid: None,
         }
     }
 
@@ -128,7 +344,35 @@ impl<'a> Widget for TextBox<'a> {
     /// Update the state of the TextEdit.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { idx, state, rect, style, mut ui, .. } = args;
-        let TextBox { text, .. } = self;
+        let TextBox {
+            text,
+            maybe_key_bindings,
+            maybe_filter,
+            maybe_validator,
+            maybe_max_len,
+            maybe_mask,
+            maybe_password_char,
+            maybe_placeholder,
+            flag_invalid,
+            ..
+        } = self;
+
+        // Whether or not the given text is valid.
+        //
+        // Empty text is not checked against the `Filter` so that a blank field is not flagged as
+        // invalid before anything has been entered.
+        let is_valid = |text: &str| -> bool {
+            (text.is_empty() || maybe_filter.map_or(true, |filter| filter.is_valid(text)))
+                && maybe_validator.as_ref().map_or(true, |validator| validator(text))
+        };
+
+        // Whether or not the given text should be accepted as an edit.
+        let is_acceptable = |text: &str| -> bool {
+            maybe_max_len.map_or(true, |max_len| text.chars().count() <= max_len)
+                && (flag_invalid
+                    || maybe_filter.map_or(true, |filter| filter.accepts(text))
+                    && maybe_validator.as_ref().map_or(true, |validator| validator(text)))
+        };
 
         let font_size = style.font_size(ui.theme());
         let border = style.border(ui.theme());
@@ -145,7 +389,9 @@ impl<'a> Widget for TextBox<'a> {
 
         let rectangle_idx = state.rectangle_idx.get(&mut ui);
         let color = style.color(ui.theme());
-        let border_color = style.border_color(ui.theme());
+        let text_is_valid = is_valid(text);
+        let border_color = if text_is_valid { style.border_color(ui.theme()) }
+                           else             { style.invalid_border_color(ui.theme()) };
         widget::BorderedRectangle::new(rect.dim())
             .xy(rect.xy())
             .graphics_for(idx)
@@ -156,21 +402,43 @@ impl<'a> Widget for TextBox<'a> {
             .set(rectangle_idx, &mut ui);
 
         let mut events = Vec::new();
+        let mut new_is_valid = text_is_valid;
 
         let text_edit_idx = state.text_edit_idx.get(&mut ui);
+
+        // When focused, pass keyboard capture on to the `TextEdit` so that it may be edited.
+        if ui.global_input().current.widget_capturing_keyboard == Some(idx) {
+            ui.set_keyboard_focus(text_edit_idx);
+        }
+
         let text_color = style.text_color(ui.theme());
+        let placeholder_color = style.placeholder_color(ui.theme());
+        let placeholder_font_id = style.placeholder_font_id(ui.theme());
         if let Some(new_string) = widget::TextEdit::new(text)
             .wh(text_rect.dim())
             .xy(text_rect.xy())
             .font_size(font_size)
             .color(text_color)
             .x_align_text(x_align)
+            .and_then(maybe_key_bindings, |text_edit, kb| text_edit.key_bindings(kb))
+            .and_then(maybe_mask, |text_edit, mask| text_edit.mask(mask))
+            .filter(&is_acceptable)
+            .and_then(maybe_password_char, |text_edit, c| text_edit.password_char(c))
+            .and_then(maybe_placeholder, |text_edit, p| text_edit.placeholder(p))
+            .and_then(placeholder_color, |text_edit, c| text_edit.placeholder_color(c))
+            .and_then(placeholder_font_id, |text_edit, id| text_edit.placeholder_font_id(id))
             .parent(idx)
             .set(text_edit_idx, &mut ui)
         {
+            new_is_valid = is_valid(&new_string);
             events.push(Event::Update(new_string));
         }
 
+        if state.maybe_is_valid != Some(new_is_valid) {
+            state.update(|state| state.maybe_is_valid = Some(new_is_valid));
+            events.push(Event::Validity(new_is_valid));
+        }
+
         // Produce an event for any `Enter`/`Return` presses.
         //
         // TODO: We should probably be doing this via the `TextEdit` widget.
@@ -202,3 +470,28 @@ impl<'a> Borderable for TextBox<'a> {
 impl<'a> Colorable for TextBox<'a> {
     builder_method!(color { style.color = Some(Color) });
 }
+
+
+#[test]
+fn filters() {
+    assert!(Filter::Integer.accepts("-"));
+    assert!(!Filter::Integer.is_valid("-"));
+    assert!(Filter::Integer.is_valid("-42"));
+    assert!(!Filter::Integer.accepts("4.2"));
+
+    let float = Filter::Float { min: Some(0.0), max: Some(1.0) };
+    assert!(float.accepts("0."));
+    assert!(float.is_valid("0.5"));
+    assert!(!float.is_valid("1.5"));
+    assert!(!float.accepts("0.5.1"));
+
+    assert!(Filter::Hex.is_valid("00ff7F"));
+    assert!(!Filter::Hex.accepts("0xff"));
+
+    let ident = Filter::CharClass("[a-zA-Z0-9_]");
+    assert!(ident.is_valid("foo_Bar9"));
+    assert!(!ident.accepts("foo bar"));
+    let no_spaces = Filter::CharClass("[^ ]");
+    assert!(no_spaces.accepts("foo-bar"));
+    assert!(!no_spaces.accepts("foo bar"));
+}
@@ -0,0 +1,118 @@
+//! Searching for and replacing literal matches within the text of a `TextEdit`.
+
+use std;
+
+
+/// Whether or not a `Query` distinguishes between upper and lower case letters.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Case {
+    /// Letters only match letters of the same case.
+    Sensitive,
+    /// Letters match letters of either case.
+    Insensitive,
+}
+
+/// A literal string to search for within some text.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Query<'a> {
+    /// The string to search for.
+    pub string: &'a str,
+    /// Whether or not the search distinguishes between upper and lower case letters.
+    pub case: Case,
+}
+
+
+impl<'a> Query<'a> {
+
+    /// A case sensitive search for the given string.
+    pub fn new(string: &'a str) -> Self {
+        Query {
+            string: string,
+            case: Case::Sensitive,
+        }
+    }
+
+    /// A case insensitive search for the given string.
+    pub fn case_insensitive(string: &'a str) -> Self {
+        Query {
+            string: string,
+            case: Case::Insensitive,
+        }
+    }
+
+    /// If the query matches the start of the given text, returns the length in bytes of the match.
+    fn match_len(&self, text: &str) -> Option<usize> {
+        let mut text_chars = text.char_indices();
+        for query_char in self.string.chars() {
+            match text_chars.next() {
+                Some((_, text_char)) if chars_eq(query_char, text_char, self.case) => (),
+                _ => return None,
+            }
+        }
+        Some(text_chars.next().map(|(i, _)| i).unwrap_or(text.len()))
+    }
+
+    /// The byte range of each non-overlapping match within the given text in order.
+    ///
+    /// An empty query has no matches.
+    pub fn matches(&self, text: &str) -> Vec<std::ops::Range<usize>> {
+        let mut matches = Vec::new();
+        if self.string.is_empty() {
+            return matches;
+        }
+        let mut start = 0;
+        while start < text.len() {
+            match self.match_len(&text[start..]) {
+                Some(len) => {
+                    matches.push(start..start + len);
+                    start += len;
+                },
+                None => {
+                    start += text[start..].chars().next().map(|c| c.len_utf8()).unwrap_or(1);
+                },
+            }
+        }
+        matches
+    }
+
+    /// Whether or not the given text is exactly a match for the query.
+    pub fn is_match(&self, text: &str) -> bool {
+        !self.string.is_empty() && self.match_len(text) == Some(text.len())
+    }
+
+    /// Replace every match within the given text with the given replacement.
+    pub fn replace_all(&self, text: &str, replacement: &str) -> String {
+        let mut replaced = String::with_capacity(text.len());
+        let mut end = 0;
+        for range in self.matches(text) {
+            replaced.push_str(&text[end..range.start]);
+            replaced.push_str(replacement);
+            end = range.end;
+        }
+        replaced.push_str(&text[end..]);
+        replaced
+    }
+
+}
+
+/// Whether or not the given chars are equal with respect to the given `Case`.
+fn chars_eq(a: char, b: char, case: Case) -> bool {
+    match case {
+        Case::Sensitive => a == b,
+        Case::Insensitive => a == b || a.to_lowercase().eq(b.to_lowercase()),
+    }
+}
+
+
+#[test]
+fn query_matches() {
+    let text = "Foo foo fOO";
+    assert_eq!(Query::new("foo").matches(text), vec![4..7]);
+    assert_eq!(Query::case_insensitive("foo").matches(text), vec![0..3, 4..7, 8..11]);
+    assert_eq!(Query::new("aa").matches("aaa"), vec![0..2]);
+    assert_eq!(Query::new("").matches(text), vec![]);
+    assert_eq!(Query::case_insensitive("é").matches("ÉéE"), vec![0..2, 2..4]);
+    assert_eq!(Query::case_insensitive("foo").replace_all(text, "bar"), "bar bar bar");
+    assert!(Query::case_insensitive("foo").is_match("FOO"));
+    assert!(!Query::new("foo").is_match("foo "));
+}
@@ -0,0 +1,222 @@
+//! A table mapping key presses to the editing `Command`s supported by the `TextEdit` widget.
+//!
+//! Each platform has its own conventions for text editing shortcuts. `KeyBindings::default`
+//! returns the preset for the target platform, though users may start from any preset (or from an
+//! empty table) and override individual entries.
+
+use input::Key;
+use input::keyboard::{ModifierKey, ALT, CTRL, GUI, NO_MODIFIER, SHIFT};
+use std;
+
+
+/// Movements of the cursor that may be performed by a `Command`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Motion {
+    /// The previous character.
+    Left,
+    /// The next character.
+    Right,
+    /// The start of the previous word.
+    WordLeft,
+    /// The end of the next word.
+    WordRight,
+    /// The closest position on the line above.
+    Up,
+    /// The closest position on the line below.
+    Down,
+    /// The start of the current line.
+    LineStart,
+    /// The end of the current line.
+    LineEnd,
+    /// The start of the text.
+    TextStart,
+    /// The end of the text.
+    TextEnd,
+}
+
+/// The editing commands that may be triggered via the keyboard.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Command {
+    /// Move the cursor, deselecting any selected text.
+    Move(Motion),
+    /// Move the end of the selection, leaving its start anchored.
+    Select(Motion),
+    /// Select all text.
+    SelectAll,
+    /// Delete the selection, or the character before the cursor.
+    DeleteBackward,
+    /// Delete the selection, or the character after the cursor.
+    DeleteForward,
+    /// Delete the selection, or up to the start of the word before the cursor.
+    DeleteWordBackward,
+    /// Delete the selection, or up to the end of the word after the cursor.
+    DeleteWordForward,
+    /// Insert a line break.
+    Newline,
+    /// Indent each line touched by the selection, or insert an indent at the cursor.
+    Indent,
+    /// Remove one level of indentation from each line touched by the selection or cursor.
+    Outdent,
+    /// Undo the last edit.
+    Undo,
+    /// Redo the last undone edit.
+    Redo,
+    /// Copy the selection to the clipboard.
+    Copy,
+    /// Copy the selection to the clipboard and delete it.
+    Cut,
+    /// Insert the contents of the clipboard.
+    Paste,
+}
+
+/// A table mapping `(Key, ModifierKey)` pairs to editing `Command`s.
+///
+/// The modifiers must match exactly, i.e. a binding for `Ctrl+Left` will not be triggered by
+/// `Ctrl+Shift+Left`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyBindings {
+    map: std::collections::HashMap<(Key, ModifierKey), Command>,
+}
+
+
+impl KeyBindings {
+
+    /// An empty table with no bindings.
+    pub fn new() -> Self {
+        KeyBindings {
+            map: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Bind the given key and modifiers to the given command, replacing any existing binding.
+    pub fn bind(mut self, key: Key, modifiers: ModifierKey, command: Command) -> Self {
+        self.insert(key, modifiers, command);
+        self
+    }
+
+    /// Bind the given key and modifiers to the given command.
+    ///
+    /// Returns the command that was previously bound to the key and modifiers, if any.
+    pub fn insert(&mut self, key: Key, modifiers: ModifierKey, command: Command) -> Option<Command> {
+        self.map.insert((key, modifiers), command)
+    }
+
+    /// Remove the binding for the given key and modifiers.
+    ///
+    /// Returns the command that was bound to the key and modifiers, if any.
+    pub fn remove(&mut self, key: Key, modifiers: ModifierKey) -> Option<Command> {
+        self.map.remove(&(key, modifiers))
+    }
+
+    /// The command bound to the given key and modifiers, if any.
+    pub fn command(&self, key: Key, modifiers: ModifierKey) -> Option<Command> {
+        self.map.get(&(key, modifiers)).cloned()
+    }
+
+    /// Produce an iterator yielding each bound key and modifiers along with its command.
+    pub fn bindings(&self) -> std::collections::hash_map::Iter<(Key, ModifierKey), Command> {
+        self.map.iter()
+    }
+
+    /// Bindings that are shared by all platforms.
+    fn common() -> Self {
+        KeyBindings::new()
+            .bind(Key::Left, NO_MODIFIER, Command::Move(Motion::Left))
+            .bind(Key::Right, NO_MODIFIER, Command::Move(Motion::Right))
+            .bind(Key::Up, NO_MODIFIER, Command::Move(Motion::Up))
+            .bind(Key::Down, NO_MODIFIER, Command::Move(Motion::Down))
+            .bind(Key::Left, SHIFT, Command::Select(Motion::Left))
+            .bind(Key::Right, SHIFT, Command::Select(Motion::Right))
+            .bind(Key::Up, SHIFT, Command::Select(Motion::Up))
+            .bind(Key::Down, SHIFT, Command::Select(Motion::Down))
+            .bind(Key::Backspace, NO_MODIFIER, Command::DeleteBackward)
+            .bind(Key::Backspace, SHIFT, Command::DeleteBackward)
+            .bind(Key::Delete, NO_MODIFIER, Command::DeleteForward)
+            .bind(Key::Return, NO_MODIFIER, Command::Newline)
+            .bind(Key::Return, SHIFT, Command::Newline)
+    }
+
+    /// The conventional bindings for Windows.
+    pub fn windows() -> Self {
+        KeyBindings::common()
+            .bind(Key::Left, CTRL, Command::Move(Motion::WordLeft))
+            .bind(Key::Right, CTRL, Command::Move(Motion::WordRight))
+            .bind(Key::Left, CTRL | SHIFT, Command::Select(Motion::WordLeft))
+            .bind(Key::Right, CTRL | SHIFT, Command::Select(Motion::WordRight))
+            .bind(Key::Home, NO_MODIFIER, Command::Move(Motion::LineStart))
+            .bind(Key::End, NO_MODIFIER, Command::Move(Motion::LineEnd))
+            .bind(Key::Home, SHIFT, Command::Select(Motion::LineStart))
+            .bind(Key::End, SHIFT, Command::Select(Motion::LineEnd))
+            .bind(Key::Home, CTRL, Command::Move(Motion::TextStart))
+            .bind(Key::End, CTRL, Command::Move(Motion::TextEnd))
+            .bind(Key::Home, CTRL | SHIFT, Command::Select(Motion::TextStart))
+            .bind(Key::End, CTRL | SHIFT, Command::Select(Motion::TextEnd))
+            .bind(Key::A, CTRL, Command::SelectAll)
+            .bind(Key::Backspace, CTRL, Command::DeleteWordBackward)
+            .bind(Key::Delete, CTRL, Command::DeleteWordForward)
+            .bind(Key::Z, CTRL, Command::Undo)
+            .bind(Key::Y, CTRL, Command::Redo)
+            .bind(Key::Z, CTRL | SHIFT, Command::Redo)
+            .bind(Key::C, CTRL, Command::Copy)
+            .bind(Key::X, CTRL, Command::Cut)
+            .bind(Key::V, CTRL, Command::Paste)
+            .bind(Key::Insert, CTRL, Command::Copy)
+            .bind(Key::Delete, SHIFT, Command::Cut)
+            .bind(Key::Insert, SHIFT, Command::Paste)
+    }
+
+    /// The conventional bindings for Linux and other unix-like desktops.
+    ///
+    /// These are the same as the Windows bindings, as followed by both GTK and Qt.
+    pub fn linux() -> Self {
+        KeyBindings::windows()
+    }
+
+    /// The conventional bindings for OS X.
+    ///
+    /// Most commands use `Cmd` (the `GUI` modifier) and word movement uses `Alt`. The emacs-style
+    /// `Ctrl+A` and `Ctrl+E` move to the start and end of the line.
+    pub fn mac() -> Self {
+        KeyBindings::common()
+            .bind(Key::Left, ALT, Command::Move(Motion::WordLeft))
+            .bind(Key::Right, ALT, Command::Move(Motion::WordRight))
+            .bind(Key::Left, ALT | SHIFT, Command::Select(Motion::WordLeft))
+            .bind(Key::Right, ALT | SHIFT, Command::Select(Motion::WordRight))
+            .bind(Key::Left, GUI, Command::Move(Motion::LineStart))
+            .bind(Key::Right, GUI, Command::Move(Motion::LineEnd))
+            .bind(Key::Left, GUI | SHIFT, Command::Select(Motion::LineStart))
+            .bind(Key::Right, GUI | SHIFT, Command::Select(Motion::LineEnd))
+            .bind(Key::Up, GUI, Command::Move(Motion::TextStart))
+            .bind(Key::Down, GUI, Command::Move(Motion::TextEnd))
+            .bind(Key::Up, GUI | SHIFT, Command::Select(Motion::TextStart))
+            .bind(Key::Down, GUI | SHIFT, Command::Select(Motion::TextEnd))
+            .bind(Key::Home, NO_MODIFIER, Command::Move(Motion::TextStart))
+            .bind(Key::End, NO_MODIFIER, Command::Move(Motion::TextEnd))
+            .bind(Key::Home, SHIFT, Command::Select(Motion::TextStart))
+            .bind(Key::End, SHIFT, Command::Select(Motion::TextEnd))
+            .bind(Key::A, CTRL, Command::Move(Motion::LineStart))
+            .bind(Key::E, CTRL, Command::Move(Motion::LineEnd))
+            .bind(Key::A, GUI, Command::SelectAll)
+            .bind(Key::Backspace, ALT, Command::DeleteWordBackward)
+            .bind(Key::Delete, ALT, Command::DeleteWordForward)
+            .bind(Key::Z, GUI, Command::Undo)
+            .bind(Key::Z, GUI | SHIFT, Command::Redo)
+            .bind(Key::C, GUI, Command::Copy)
+            .bind(Key::X, GUI, Command::Cut)
+            .bind(Key::V, GUI, Command::Paste)
+    }
+
+}
+
+impl Default for KeyBindings {
+    /// The preset for the target platform.
+    fn default() -> Self {
+        if cfg!(target_os = "macos") {
+            KeyBindings::mac()
+        } else if cfg!(target_os = "windows") {
+            KeyBindings::windows()
+        } else {
+            KeyBindings::linux()
+        }
+    }
+}
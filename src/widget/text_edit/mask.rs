@@ -0,0 +1,102 @@
+//! Formatting of text via an input mask, e.g. `##:##:##` for a time or `####-##-##` for a date.
+//!
+//! Within a mask, the following characters are "slots" that must be filled by user input:
+//!
+//! - `#` any decimal digit.
+//! - `A` any alphabetic character.
+//! - `*` any alphanumeric character.
+//! - `?` any character.
+//!
+//! All other characters are literals which are inserted automatically as the user types.
+
+
+/// Whether or not the given mask char is a slot rather than a literal.
+fn is_slot(mask_char: char) -> bool {
+    match mask_char {
+        '#' | 'A' | '*' | '?' => true,
+        _ => false,
+    }
+}
+
+/// Whether or not the given char may fill the given slot.
+fn fits_slot(slot: char, c: char) -> bool {
+    match slot {
+        '#' => c.is_digit(10),
+        'A' => c.is_alphabetic(),
+        '*' => c.is_alphanumeric(),
+        _ => true,
+    }
+}
+
+/// Format the given `text` with the given `mask`.
+///
+/// Any literals already within the `text` are discarded and the remaining characters are used to
+/// fill the mask's slots in order. Literals are only inserted if they are followed by some input.
+///
+/// `cursor_char_idx` is the char index of the cursor within the given `text`. The index of the
+/// cursor within the formatted text is returned alongside it.
+///
+/// Returns `None` if some char does not fit its slot or if there are more chars than slots.
+pub fn apply(mask: &str, text: &str, cursor_char_idx: usize) -> Option<(String, usize)> {
+    let is_literal = |c: char| mask.chars().any(|m| !is_slot(m) && m == c);
+
+    // The chars that fill the slots, along with the number of them preceding the cursor.
+    let mut input_before_cursor = 0;
+    let mut input = Vec::new();
+    for (i, c) in text.chars().enumerate() {
+        if is_literal(c) {
+            continue;
+        }
+        if i < cursor_char_idx {
+            input_before_cursor += 1;
+        }
+        input.push(c);
+    }
+
+    let mut formatted = String::new();
+    let mut formatted_char_count = 0;
+    let mut num_consumed = 0;
+    let mut new_cursor_char_idx = 0;
+    let mut input = input.into_iter().peekable();
+    for m in mask.chars() {
+        if input.peek().is_none() {
+            break;
+        }
+        if is_slot(m) {
+            let c = input.next().unwrap();
+            if !fits_slot(m, c) {
+                return None;
+            }
+            formatted.push(c);
+            formatted_char_count += 1;
+            num_consumed += 1;
+            // The cursor follows the input char that it followed prior to formatting.
+            if num_consumed == input_before_cursor {
+                new_cursor_char_idx = formatted_char_count;
+            }
+        } else {
+            formatted.push(m);
+            formatted_char_count += 1;
+        }
+    }
+
+    // Too much input for the number of slots.
+    if input.peek().is_some() {
+        return None;
+    }
+
+    Some((formatted, new_cursor_char_idx))
+}
+
+
+#[test]
+fn apply_mask() {
+    assert_eq!(apply("##:##", "1", 1), Some(("1".to_owned(), 1)));
+    assert_eq!(apply("##:##", "12", 2), Some(("12".to_owned(), 2)));
+    assert_eq!(apply("##:##", "123", 3), Some(("12:3".to_owned(), 4)));
+    assert_eq!(apply("##:##", "12:34", 5), Some(("12:34".to_owned(), 5)));
+    // Removing a digit from the middle shifts the remaining digits back through the mask.
+    assert_eq!(apply("##:##", "1:34", 1), Some(("13:4".to_owned(), 1)));
+    assert_eq!(apply("##:##", "12a", 3), None);
+    assert_eq!(apply("##:##", "12345", 5), None);
+}
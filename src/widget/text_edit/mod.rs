@@ -0,0 +1,2439 @@
+//! A widget for displaying and mutating multi-line text, given as a `String`.
+
+use {
+    color,
+    Align,
+    Color,
+    Colorable,
+    FontSize,
+    NodeIndex,
+    Point,
+    Positionable,
+    Range,
+    Rect,
+    Scalar,
+    Sizeable,
+    Ui,
+    UiCell,
+    Widget,
+};
+use cursor::MouseCursor;
+use event;
+use input;
+use std;
+use text;
+use unicode_segmentation::UnicodeSegmentation;
+use utils;
+use widget;
+use widget::primitive::text::Wrap;
+
+pub use self::find::{Case, Query};
+pub use self::key_bindings::{Command, KeyBindings, Motion};
+
+pub mod find;
+pub mod key_bindings;
+mod mask;
+
+
+/// A widget for displaying and mutating multi-line text, given as a `String`.
+///
+/// By default the text is wrapped via the first whitespace before the line exceeds the
+/// `TextEdit`'s width, however a user may change this using the `.wrap_by_character` method.
+///
+/// Double-clicking selects the word under the mouse, while triple-clicking selects its line.
+///
+/// If `restrict_to_height` is `false`, text that exceeds the height of the `TextEdit` may be
+/// scrolled. The `TextEdit` scrolls automatically to keep the cursor in view.
+///
+/// Matches for a `find::Query` may be highlighted, stepped through and replaced via the `find`
+/// family of builder methods. See the `FindBar` widget for a ready-made interface.
+pub struct TextEdit<'a> {
+    common: widget::CommonBuilder,
+    text: &'a str,
+    style: Style,
+    clear_history: bool,
+    maybe_key_bindings: Option<&'a KeyBindings>,
+    maybe_mask: Option<&'a str>,
+    maybe_filter: Option<Box<Fn(&str) -> bool + 'a>>,
+    maybe_password_char: Option<char>,
+    maybe_placeholder: Option<&'a str>,
+    maybe_highlighter: Option<Box<Fn(&str) -> Vec<Span> + 'a>>,
+    maybe_query: Option<Query<'a>>,
+    maybe_find_step: Option<FindStep>,
+    maybe_replace: Option<Replace<'a>>,
+    indent: &'a str,
+    auto_indent: bool,
+    accepts_tab: bool,
+    tab_indents: bool,
+}
+
+widget_style!{
+    /// Unique graphical styling for the TextEdit.
+    style Style {
+        /// The color of the text (this includes cursor and selection color).
+        - color: Color { theme.shape_color }
+        /// The font size for the text.
+        - font_size: FontSize { theme.font_size_medium }
+        /// The horizontal alignment of the text.
+        - x_align: Align { Align::Start }
+        /// The vertical alignment of the text.
+        - y_align: Align { Align::End }
+        /// The vertical space between each line of text.
+        - line_spacing: Scalar { 1.0 }
+        /// The way in which text is wrapped at the end of a line.
+        - line_wrap: Wrap { Wrap::Whitespace }
+        /// Do not allow to enter text that would exceed the bounds of the `TextEdit`'s `Rect`.
+        ///
+        /// If `false`, text that exceeds the bounds may be scrolled.
+        - restrict_to_height: bool { true }
+        /// The font used for the `Text`.
+        - font_id: Option<text::font::Id> { theme.font_id }
+        /// The maximum number of edits that may be undone.
+        - history_limit: usize { 128 }
+        /// The color of the placeholder text.
+        ///
+        /// If `None`, the `color` is used with a reduced alpha.
+        - placeholder_color: Option<Color> { None }
+        /// The font used for the placeholder text.
+        ///
+        /// If `None`, the `font_id` is used.
+        - placeholder_font_id: Option<text::font::Id> { None }
+        /// The color used to highlight matches for the `find` query.
+        - match_color: Color { color::YELLOW }
+    }
+}
+
+/// The State of the TextEdit widget that will be cached within the Ui.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    cursor: Cursor,
+    /// Track whether some sort of dragging is currently occurring.
+    drag: Option<Drag>,
+    /// Information about each line of text.
+    line_infos: Vec<text::line::Info>,
+    selected_rectangle_indices: Vec<NodeIndex>,
+    rectangle_idx: widget::IndexSlot,
+    text_idx: widget::IndexSlot,
+    cursor_idx: widget::IndexSlot,
+    highlight_idx: widget::IndexSlot,
+    scroll_area_idx: widget::IndexSlot,
+    placeholder_idx: widget::IndexSlot,
+    /// Indices for the `Text` widgets used to draw each run of highlighted text.
+    span_text_indices: Vec<NodeIndex>,
+    /// Indices for the `Rectangle`s used to highlight matches for the `find` query.
+    match_rectangle_indices: Vec<NodeIndex>,
+    /// Indices for the `Line`s used to underline the input method's pre-edit text.
+    pre_edit_underline_indices: Vec<NodeIndex>,
+    /// The text currently being composed by the input method, if any.
+    maybe_pre_edit: Option<event::PreEdit>,
+    /// The undo and redo stacks for edits made via the `TextEdit`.
+    history: History,
+}
+
+/// Track whether some sort of dragging is currently occurring.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Drag {
+    /// The drag is currently selecting a range of text.
+    Selecting,
+    /// The drag is moving a selection of text.
+    #[allow(dead_code)] // TODO: Implement this.
+    MoveSelection,
+}
+
+/// The position of the `Cursor` over the text.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Cursor {
+    /// The cursor is at the given character index.
+    Idx(text::cursor::Index),
+    /// The cursor is a selection between these two indices.
+    Selection {
+        /// The `start` is always the "anchor" point.
+        start: text::cursor::Index,
+        /// The `end` may be either greater or less than the `start`.
+        end: text::cursor::Index,
+    },
+}
+
+/// The direction in which to step to the next match for the `find` query.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum FindStep {
+    Next,
+    Previous,
+}
+
+/// The replacement of matches for the `find` query.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Replace<'a> {
+    /// Replace the selected match.
+    Selection(&'a str),
+    /// Replace every match.
+    All(&'a str),
+}
+
+/// A range of text to be drawn with a unique style, as produced by a `TextEdit`'s highlighter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Span {
+    /// The range of bytes within the text to which the style applies.
+    pub range: std::ops::Range<usize>,
+    /// The color of the text within the range.
+    pub color: Color,
+    /// The font used for the text within the range.
+    ///
+    /// If `None`, the `TextEdit`'s font is used. Note that glyphs are always positioned using
+    /// the `TextEdit`'s font, so fonts with differing metrics may appear misaligned.
+    pub font_id: Option<text::font::Id>,
+}
+
+/// The undo and redo history for a `TextEdit`.
+///
+/// Each entry is a snapshot of the text and cursor as they were prior to an edit. Consecutive
+/// typed text is coalesced into a single entry so that undo removes whole runs of typing, rather
+/// than one character at a time.
+#[derive(Clone, Debug, PartialEq)]
+struct History {
+    undos: Vec<Snapshot>,
+    redos: Vec<Snapshot>,
+    /// The kind of the last recorded edit. Used for coalescing consecutive typing.
+    last_edit: Option<Edit>,
+}
+
+/// A snapshot of the `TextEdit`'s text and cursor.
+#[derive(Clone, Debug, PartialEq)]
+struct Snapshot {
+    text: String,
+    cursor: Cursor,
+}
+
+/// The kinds of edits that may be recorded by the `History`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Edit {
+    /// Text was inserted via `Text` events. Consecutive `Typing` edits are coalesced.
+    Typing,
+    /// Any other edit, i.e. deletion or the insertion of a newline.
+    Other,
+}
+
+
+impl History {
+
+    /// An empty history.
+    fn new() -> Self {
+        History {
+            undos: Vec::new(),
+            redos: Vec::new(),
+            last_edit: None,
+        }
+    }
+
+    /// Whether or not the given `edit` would produce a new entry if recorded.
+    fn would_record(&self, edit: Edit) -> bool {
+        !(edit == Edit::Typing && self.last_edit == Some(Edit::Typing))
+    }
+
+    /// Record the `text` and `cursor` as they were prior to the given `edit`.
+    ///
+    /// The oldest entries are dropped if the number of entries would exceed the `limit`.
+    fn record(&mut self, edit: Edit, text: &str, cursor: Cursor, limit: usize) {
+        if self.would_record(edit) {
+            self.undos.push(Snapshot { text: text.to_owned(), cursor: cursor });
+            self.redos.clear();
+        }
+        self.last_edit = Some(edit);
+        self.cap(limit);
+    }
+
+    /// Drop the oldest entries until there are no more than `limit` in each stack.
+    fn cap(&mut self, limit: usize) {
+        if self.undos.len() > limit {
+            let excess = self.undos.len() - limit;
+            self.undos.drain(0..excess);
+        }
+        if self.redos.len() > limit {
+            let excess = self.redos.len() - limit;
+            self.redos.drain(0..excess);
+        }
+    }
+
+    /// Ensure that the next edit produces a new entry, even if it is `Typing`.
+    fn end_edit(&mut self) {
+        self.last_edit = None;
+    }
+
+    /// Pop the most recent entry from the undo stack, pushing the given current `text` and
+    /// `cursor` onto the redo stack.
+    fn undo(&mut self, text: &str, cursor: Cursor) -> Option<Snapshot> {
+        self.undos.pop().map(|snapshot| {
+            self.redos.push(Snapshot { text: text.to_owned(), cursor: cursor });
+            self.last_edit = None;
+            snapshot
+        })
+    }
+
+    /// Pop the most recent entry from the redo stack, pushing the given current `text` and
+    /// `cursor` onto the undo stack.
+    fn redo(&mut self, text: &str, cursor: Cursor) -> Option<Snapshot> {
+        self.redos.pop().map(|snapshot| {
+            self.undos.push(Snapshot { text: text.to_owned(), cursor: cursor });
+            self.last_edit = None;
+            snapshot
+        })
+    }
+
+    /// Remove all entries from the history.
+    fn clear(&mut self) {
+        self.undos.clear();
+        self.redos.clear();
+        self.last_edit = None;
+    }
+
+}
+
+
+impl State {
+
+    /// The current position of the cursor or selection.
+    pub fn cursor(&self) -> Cursor {
+        self.cursor
+    }
+
+    /// Information about each line of the text as it was last laid out.
+    pub fn line_infos(&self) -> &[text::line::Info] {
+        &self.line_infos
+    }
+
+}
+
+
+impl<'a> TextEdit<'a> {
+
+    /// Construct a TextEdit widget.
+    pub fn new(text: &'a str) -> Self {
+        TextEdit {
+            common: widget::CommonBuilder::new(),
+            text: text,
+            style: Style::new(),
+            clear_history: false,
+            maybe_key_bindings: None,
+            maybe_mask: None,
+            maybe_filter: None,
+            maybe_password_char: None,
+            maybe_placeholder: None,
+            maybe_highlighter: None,
+            maybe_query: None,
+            maybe_find_step: None,
+            maybe_replace: None,
+            indent: "\t",
+            auto_indent: false,
+            accepts_tab: false,
+            tab_indents: false,
+        }
+    }
+
+    /// Highlight the text with the `Span`s produced by the given function.
+    ///
+    /// The function is called with the current text each time the `TextEdit` is updated. Text
+    /// that is not covered by a `Span` is drawn with the `TextEdit`'s color. Where `Span`s
+    /// overlap, the latter takes precedence. `Span`s whose ranges do not fall on `char`
+    /// boundaries are ignored.
+    ///
+    /// Highlighting is disabled while in `password` mode.
+    pub fn highlighter<F>(mut self, highlighter: F) -> Self
+        where F: Fn(&str) -> Vec<Span> + 'a,
+    {
+        self.maybe_highlighter = Some(Box::new(highlighter));
+        self
+    }
+
+    /// The font used for the text.
+    ///
+    /// By default, the `Theme`'s `font_id` is used.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.font_id = Some(Some(font_id));
+        self
+    }
+
+    /// Hint text that is displayed while the text is empty and the `TextEdit` is not capturing
+    /// the keyboard.
+    pub fn placeholder(mut self, placeholder: &'a str) -> Self {
+        self.maybe_placeholder = Some(placeholder);
+        self
+    }
+
+    /// The color of the `placeholder` text.
+    ///
+    /// By default, the `TextEdit`'s color is used with a reduced alpha.
+    pub fn placeholder_color(mut self, color: Color) -> Self {
+        self.style.placeholder_color = Some(Some(color));
+        self
+    }
+
+    /// The font used for the `placeholder` text.
+    ///
+    /// By default, the `TextEdit`'s font is used.
+    pub fn placeholder_font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.placeholder_font_id = Some(Some(font_id));
+        self
+    }
+
+    /// Obscure the text for password entry, displaying each grapheme as a `•`.
+    ///
+    /// The real text is still produced by the `TextEdit`, however the text may not be copied or cut
+    /// to the clipboard.
+    pub fn password(self) -> Self {
+        self.password_char('•')
+    }
+
+    /// Obscure the text for password entry, displaying each grapheme as the given `char`.
+    ///
+    /// The real text is still produced by the `TextEdit`, however the text may not be copied or cut
+    /// to the clipboard.
+    pub fn password_char(mut self, password_char: char) -> Self {
+        self.maybe_password_char = Some(password_char);
+        self
+    }
+
+    /// Format the text with the given input mask as it is entered, e.g. `##:##:##`.
+    ///
+    /// Within the mask, `#` is filled by a digit, `A` by a letter, `*` by a letter or digit and
+    /// `?` by any character. All other characters are literals that are inserted automatically.
+    ///
+    /// Edits that would produce text that does not fit the mask are rejected.
+    pub fn mask(mut self, mask: &'a str) -> Self {
+        self.maybe_mask = Some(mask);
+        self
+    }
+
+    /// Reject any edit that would produce text for which the given function returns `false`.
+    ///
+    /// Rejected edits leave the text, cursor and undo history untouched.
+    pub fn filter<F>(mut self, filter: F) -> Self
+        where F: Fn(&str) -> bool + 'a,
+    {
+        self.maybe_filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Use the given `KeyBindings` to map key presses to editing `Command`s.
+    ///
+    /// By default, the `Ui`'s `KeyBindings` are used (see `Ui::key_bindings`).
+    pub fn key_bindings(mut self, key_bindings: &'a KeyBindings) -> Self {
+        self.maybe_key_bindings = Some(key_bindings);
+        self
+    }
+
+    /// The string inserted by the `Indent` command and removed by the `Outdent` command.
+    ///
+    /// By default, this is a single tab.
+    pub fn indent(mut self, indent: &'a str) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Whether or not `Tab` enters a tab into the text while the `TextEdit` has keyboard focus.
+    ///
+    /// By default, `Tab` moves keyboard focus to the next focusable widget unless it is bound to a
+    /// command, i.e. `Indent`. If `true`, `Tab` enters a tab instead and keyboard focus may be
+    /// moved via `Ctrl+Tab`.
+    pub fn accepts_tab(mut self, accepts_tab: bool) -> Self {
+        self.accepts_tab = accepts_tab;
+        self
+    }
+
+    /// Whether or not `Tab` and `Shift+Tab` trigger the `Indent` and `Outdent` commands when the
+    /// key bindings do not bind `Tab` to any other command.
+    ///
+    /// By default, this is `false`.
+    pub fn tab_indents(mut self, tab_indents: bool) -> Self {
+        self.tab_indents = tab_indents;
+        self
+    }
+
+    /// Clear the undo and redo history.
+    ///
+    /// This is useful when the text is replaced with some unrelated text (i.e. loading a new
+    /// document) and undoing back into the old text would be surprising.
+    pub fn clear_history(mut self) -> Self {
+        self.clear_history = true;
+        self
+    }
+
+    /// Highlight each match for the given `Query` within the text.
+    ///
+    /// Matches are not highlighted while in `password` mode.
+    pub fn find(mut self, query: Query<'a>) -> Self {
+        self.maybe_query = Some(query);
+        self
+    }
+
+    /// Select the first match for the `find` query following the cursor, wrapping around to the
+    /// start of the text if necessary.
+    ///
+    /// Like `clear_history`, this only applies to the update in which it is called, e.g. in
+    /// response to a button press.
+    pub fn find_next(mut self) -> Self {
+        self.maybe_find_step = Some(FindStep::Next);
+        self
+    }
+
+    /// Select the first match for the `find` query preceding the cursor, wrapping around to the
+    /// end of the text if necessary.
+    ///
+    /// This only applies to the update in which it is called.
+    pub fn find_previous(mut self) -> Self {
+        self.maybe_find_step = Some(FindStep::Previous);
+        self
+    }
+
+    /// If the selected text is a match for the `find` query, replace it with the given string and
+    /// select the next match.
+    ///
+    /// This only applies to the update in which it is called.
+    pub fn replace(mut self, replacement: &'a str) -> Self {
+        self.maybe_replace = Some(Replace::Selection(replacement));
+        self
+    }
+
+    /// Replace every match for the `find` query with the given string.
+    ///
+    /// This only applies to the update in which it is called.
+    pub fn replace_all(mut self, replacement: &'a str) -> Self {
+        self.maybe_replace = Some(Replace::All(replacement));
+        self
+    }
+
+    /// The `TextEdit` will wrap text via the whitespace that precedes the first width-exceeding
+    /// character.
+    ///
+    /// This is the default setting.
+    pub fn wrap_by_whitespace(self) -> Self {
+        self.line_wrap(Wrap::Whitespace)
+    }
+
+    /// By default, the `TextEdit` will wrap text via the whitespace that precedes the first
+    /// width-exceeding character.
+    ///
+    /// Calling this method causes the `TextEdit` to wrap text at the first exceeding character.
+    pub fn wrap_by_character(self) -> Self {
+        self.line_wrap(Wrap::Character)
+    }
+
+    /// Align the text to the left of its bounding **Rect**'s *x* axis range.
+    pub fn align_text_left(self) -> Self {
+        self.x_align_text(Align::Start)
+    }
+
+    /// Align the text to the middle of its bounding **Rect**'s *x* axis range.
+    pub fn align_text_x_middle(self) -> Self {
+        self.x_align_text(Align::Middle)
+    }
+
+    /// Align the text to the right of its bounding **Rect**'s *x* axis range.
+    pub fn align_text_right(self) -> Self {
+        self.x_align_text(Align::End)
+    }
+
+    /// Align the text to the left of its bounding **Rect**'s *y* axis range.
+    pub fn align_text_bottom(self) -> Self {
+        self.y_align_text(Align::Start)
+    }
+
+    /// Align the text to the middle of its bounding **Rect**'s *y* axis range.
+    pub fn align_text_y_middle(self) -> Self {
+        self.y_align_text(Align::Middle)
+    }
+
+    /// Align the text to the right of its bounding **Rect**'s *y* axis range.
+    pub fn align_text_top(self) -> Self {
+        self.y_align_text(Align::End)
+    }
+
+    /// Align the text to the middle of its bounding **Rect**.
+    pub fn align_text_middle(self) -> Self {
+        self.align_text_x_middle().align_text_y_middle()
+    }
+
+    builder_methods!{
+        pub font_size { style.font_size = Some(FontSize) }
+        pub x_align_text { style.x_align = Some(Align) }
+        pub y_align_text { style.y_align = Some(Align) }
+        pub line_wrap { style.line_wrap = Some(Wrap) }
+        pub line_spacing { style.line_spacing = Some(Scalar) }
+        pub restrict_to_height { style.restrict_to_height = Some(bool) }
+        pub history_limit { style.history_limit = Some(usize) }
+        pub auto_indent { auto_indent = bool }
+    }
+
+}
+
+impl<'a> Widget for TextEdit<'a> {
+    type State = State;
+    type Style = Style;
+    // TODO: We should create a more specific `Event` type that:
+    // - Allows for mutating an existing `String` directly
+    // - Enumerates possible mutations (i.e. InsertChar, RemoveCharRange, etc).
+    // - Enumerates cursor movement and range selection.
+    type Event = Option<String>;
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self) -> State {
+        State {
+            cursor: Cursor::Idx(text::cursor::Index { line: 0, char: 0 }),
+            drag: None,
+            line_infos: Vec::new(),
+            selected_rectangle_indices: Vec::new(),
+            rectangle_idx: widget::IndexSlot::new(),
+            text_idx: widget::IndexSlot::new(),
+            cursor_idx: widget::IndexSlot::new(),
+            highlight_idx: widget::IndexSlot::new(),
+            scroll_area_idx: widget::IndexSlot::new(),
+            placeholder_idx: widget::IndexSlot::new(),
+            span_text_indices: Vec::new(),
+            match_rectangle_indices: Vec::new(),
+            pre_edit_underline_indices: Vec::new(),
+            maybe_pre_edit: None,
+            history: History::new(),
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.style.clone()
+    }
+
+    /// Text that is not restricted to the height of the `TextEdit` is scrolled vertically and
+    /// cropped to its bounds, whether `restrict_to_height` is given via the builder or the `Theme`.
+    fn set<'b, 'c, I>(self, idx: I, ui: &'b mut UiCell<'c>) -> Self::Event
+        where I: Into<widget::Index>,
+    {
+        let text_edit = if self.style.restrict_to_height(&ui.theme) {
+            self
+        } else {
+            self.scroll_kids_vertically()
+        };
+        widget::set_widget(text_edit, idx.into(), ui)
+    }
+
+    /// Update the state of the TextEdit.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { idx, state, rect, style, mut ui, .. } = args;
+        let TextEdit {
+            text,
+            clear_history,
+            maybe_key_bindings,
+            maybe_mask,
+            maybe_filter,
+            maybe_password_char,
+            maybe_placeholder,
+            maybe_highlighter,
+            maybe_query,
+            maybe_find_step,
+            maybe_replace,
+            indent,
+            auto_indent,
+            accepts_tab,
+            tab_indents,
+            ..
+        } = self;
+        let mut text = std::borrow::Cow::Borrowed(text);
+
+        // Retrieve the `font_id`, as long as a valid `Font` for it still exists.
+        //
+        // If we've no font to use for text logic, bail out without updating.
+        let font_id = match style.font_id(&ui.theme)
+            .or(ui.fonts.ids().next())
+            .and_then(|id| ui.fonts.get(id).map(|_| id))
+        {
+            Some(font_id) => font_id,
+            None => return None,
+        };
+
+        let font_size = style.font_size(ui.theme());
+        let line_wrap = style.line_wrap(ui.theme());
+        let x_align = style.x_align(ui.theme());
+        let y_align = style.y_align(ui.theme());
+        let line_spacing = style.line_spacing(ui.theme());
+        let restrict_to_height = style.restrict_to_height(ui.theme());
+        let history_limit = style.history_limit(ui.theme());
+        let text_idx = state.text_idx.get(&mut ui);
+
+        if clear_history && state.history != History::new() {
+            state.update(|state| state.history.clear());
+        }
+
+        /// Returns an iterator yielding the `text::line::Info` for each line in the given text
+        /// with the given styling.
+        type LineInfos<'a> = text::line::Infos<'a, text::line::NextBreakFnPtr>;
+        fn line_infos<'a>(text: &'a str,
+                          font: &'a text::Font,
+                          font_size: FontSize,
+                          line_wrap: Wrap,
+                          max_width: Scalar) -> LineInfos<'a>
+        {
+            let infos = text::line::infos(text, font, font_size);
+            match line_wrap {
+                Wrap::Whitespace => infos.wrap_by_whitespace(max_width),
+                Wrap::Character => infos.wrap_by_character(max_width),
+            }
+        }
+
+        // Check to see if the given text has changed since the last time the widget was updated.
+        {
+            let maybe_new_line_infos = {
+                let line_info_slice = &state.line_infos[..];
+                let font = ui.fonts.get(font_id).unwrap();
+                let shown = display_text(&text, maybe_password_char);
+                let new_line_infos = line_infos(&shown, font, font_size, line_wrap, rect.w());
+                match utils::write_if_different(line_info_slice, new_line_infos) {
+                    std::borrow::Cow::Owned(new) => Some(new),
+                    _ => None,
+                }
+            };
+
+            if let Some(new_line_infos) = maybe_new_line_infos {
+                state.update(|state| state.line_infos = new_line_infos);
+            }
+        }
+
+        // The offset of the text that exceeds the height of the `TextEdit`, if it is scrolled.
+        let y_scroll_offset = ui.widget_graph().widget(idx)
+            .and_then(|container| container.maybe_y_scroll_state)
+            .map(|scroll| scroll.offset)
+            .unwrap_or(0.0);
+
+        // The area occupied by the given lines of text.
+        //
+        // Text that exceeds the height of the `TextEdit` is aligned to the top of the `rect` and
+        // shifted by the scroll offset.
+        let text_area = |line_infos: &[text::line::Info]| -> Rect {
+            let height = text::height(line_infos.len(), font_size, line_spacing);
+            let y = if height > rect.h() {
+                Range::new(0.0, height).align_end_of(rect.y).shift(y_scroll_offset)
+            } else {
+                Range::new(0.0, height).align_to(y_align, rect.y)
+            };
+            Rect { x: rect.x, y: y }
+        };
+
+        // Find the position of the cursor at the given index over the given text.
+        let cursor_xy_at = |cursor_idx: text::cursor::Index,
+                            text: &str,
+                            line_infos: &[text::line::Info],
+                            font: &text::Font|
+            -> Option<(Scalar, Range)>
+        {
+            let shown = display_text(text, maybe_password_char);
+            let text: &str = &shown;
+            let xys_per_line = text::cursor::xys_per_line_from_text(text, line_infos, font,
+                                                                    font_size, x_align, y_align,
+                                                                    line_spacing,
+                                                                    text_area(line_infos));
+            text::cursor::xy_at(xys_per_line, cursor_idx)
+        };
+
+        // Find the closest cursor index to the given `xy` position.
+        //
+        // Returns `None` if the given `text` is empty.
+        let closest_cursor_index_and_xy = |xy: Point,
+                                           text: &str,
+                                           line_infos: &[text::line::Info],
+                                           font: &text::Font|
+            -> Option<(text::cursor::Index, Point)>
+        {
+            let shown = display_text(text, maybe_password_char);
+            let text: &str = &shown;
+            let xys_per_line = text::cursor::xys_per_line_from_text(text, line_infos, font,
+                                                                    font_size, x_align, y_align,
+                                                                    line_spacing,
+                                                                    text_area(line_infos));
+            text::cursor::closest_cursor_index_and_xy(xy, xys_per_line)
+        };
+
+        // Find the closest cursor index to the given `x` position over the given line.
+        let closest_cursor_index_on_line = |x_pos: Scalar,
+                                            line_idx: usize,
+                                            text: &str,
+                                            line_infos: &[text::line::Info],
+                                            font: &text::Font| -> Option<text::cursor::Index>
+        {
+            let shown = display_text(text, maybe_password_char);
+            let text: &str = &shown;
+            let mut xys_per_line = text::cursor::xys_per_line_from_text(text, line_infos, font,
+                                                                        font_size, x_align, y_align,
+                                                                        line_spacing,
+                                                                        text_area(line_infos));
+            xys_per_line.nth(line_idx).and_then(|(line_xs,_)| {
+                let (char_idx,_) = text::cursor::closest_cursor_index_on_line(x_pos,line_xs);
+                Some(text::cursor::Index { line: line_idx, char: char_idx })
+            })
+        };
+
+        // Find the cursor index produced by applying the given `motion` to the given `cursor_idx`.
+        let move_cursor = |motion: Motion,
+                           cursor_idx: text::cursor::Index,
+                           text: &str,
+                           line_infos: &[text::line::Info],
+                           font: &text::Font| -> text::cursor::Index
+        {
+            let shown = display_text(text, maybe_password_char);
+            let text: &str = &shown;
+            let infos = line_infos.iter().cloned();
+            match motion {
+                Motion::Left => cursor_idx.previous(infos).unwrap_or(cursor_idx),
+                Motion::Right => cursor_idx.next(infos).unwrap_or(cursor_idx),
+                Motion::WordLeft =>
+                    cursor_idx.previous_word_start(text, infos).unwrap_or(cursor_idx),
+                Motion::WordRight =>
+                    cursor_idx.next_word_end(text, infos).unwrap_or(cursor_idx),
+                Motion::Up | Motion::Down => {
+                    cursor_xy_at(cursor_idx, text, line_infos, font)
+                        .and_then(|(x_pos, _)| {
+                            let text::cursor::Index { line, .. } = cursor_idx;
+                            let next_line = match motion {
+                                Motion::Up => if line > 0 { line - 1 } else { 0 },
+                                _ => line + 1,
+                            };
+                            closest_cursor_index_on_line(x_pos, next_line, text, line_infos, font)
+                        })
+                        .unwrap_or(cursor_idx)
+                },
+                Motion::LineStart => text::cursor::Index { line: cursor_idx.line, char: 0 },
+                Motion::LineEnd => match line_infos.get(cursor_idx.line) {
+                    Some(info) => {
+                        let char = info.end_char() - info.start_char;
+                        text::cursor::Index { line: cursor_idx.line, char: char }
+                    },
+                    None => cursor_idx,
+                },
+                Motion::TextStart => text::cursor::Index { line: 0, char: 0 },
+                Motion::TextEnd => match line_infos.last() {
+                    Some(info) => {
+                        let char = info.end_char() - info.start_char;
+                        text::cursor::Index { line: line_infos.len() - 1, char: char }
+                    },
+                    None => cursor_idx,
+                },
+            }
+        };
+
+        // The text may have been changed by the user since the last update, so ensure that the
+        // cursor is still within range.
+        let mut cursor = clamp_cursor(state.cursor, &state.line_infos);
+        let mut drag = state.drag;
+
+        // Whether or not an edit producing the given text should be committed.
+        let is_accepted = |text: &str| -> bool {
+            maybe_filter.as_ref().map_or(true, |filter| filter(text))
+        };
+
+        // Insert the given `string` at the given `cursor` position within the given `text`.
+        //
+        // Produces the resulting text, cursor position and `line::Info`s for the new text.
+        //
+        // Returns `None` if the new text would exceed the height restriction or is rejected by the
+        // `filter`.
+        let insert_text = |string: &str,
+                           cursor: Cursor,
+                           text: &str,
+                           infos: &[text::line::Info],
+                           font: &text::Font|
+            -> Option<(String, Cursor, std::vec::Vec<text::line::Info>)>
+        {
+            let string_char_count = string.chars().count();
+
+            // Construct the new text with the new string inserted at the cursor.
+            let (new_text, new_cursor_char_idx): (String, usize) = {
+                let (cursor_start, cursor_end) = match cursor {
+                    Cursor::Idx(idx) => (idx, idx),
+                    Cursor::Selection { start, end } =>
+                        (std::cmp::min(start, end), std::cmp::max(start, end)),
+                };
+
+                let line_infos = infos.iter().cloned();
+
+                let (start_idx, end_idx) =
+                    (text::glyph::index_after_cursor(line_infos.clone(), cursor_start)
+                        .unwrap_or(0),
+                     text::glyph::index_after_cursor(line_infos.clone(), cursor_end)
+                        .unwrap_or(0));
+
+                let start_idx = text_char_index(text, maybe_password_char, start_idx);
+                let end_idx = text_char_index(text, maybe_password_char, end_idx);
+                let new_cursor_char_idx = start_idx + string_char_count;
+
+                let new_text = text.chars().take(start_idx)
+                    .chain(string.chars())
+                    .chain(text.chars().skip(end_idx))
+                    .collect();
+                (new_text, new_cursor_char_idx)
+            };
+
+            // Format the new text with the mask if there is one.
+            let (new_text, new_cursor_char_idx) = match maybe_mask {
+                Some(mask) => match mask::apply(mask, &new_text, new_cursor_char_idx) {
+                    Some(formatted) => formatted,
+                    None => return None,
+                },
+                None => (new_text, new_cursor_char_idx),
+            };
+
+            if !is_accepted(&new_text) {
+                return None;
+            }
+
+            // Calculate the new `line_infos` for the `new_text`.
+            let new_line_infos: Vec<_> = {
+                let shown = display_text(&new_text, maybe_password_char);
+                line_infos(&shown, font, font_size, line_wrap, rect.w()).collect()
+            };
+
+            // Check that the new text would not exceed the `inner_rect` bounds.
+            let num_lines = new_line_infos.len();
+            let height = text::height(num_lines, font_size, line_spacing);
+            if height < rect.h() || !restrict_to_height {
+
+                // Determine the new `Cursor` and its position.
+                let new_cursor_idx = {
+                    let line_infos = new_line_infos.iter().cloned();
+                    let new_cursor_char_idx =
+                        shown_char_index(&new_text, maybe_password_char, new_cursor_char_idx);
+                    text::cursor::index_before_char(line_infos, new_cursor_char_idx)
+                        .unwrap_or(text::cursor::Index {
+                            line: 0,
+                            char: string_char_count,
+                        })
+                };
+
+                Some((new_text, Cursor::Idx(new_cursor_idx), new_line_infos))
+            } else {
+                None
+            }
+        };
+
+        // Apply any find and replace commands given via the builder.
+        if let (Some(query), None) = (maybe_query, maybe_password_char) {
+            match maybe_replace {
+
+                Some(Replace::Selection(replacement)) => {
+                    let is_match = selected_text(cursor, &text, state.line_infos.iter().cloned())
+                        .map_or(false, |selected| query.is_match(&selected));
+                    if is_match {
+                        let font = ui.fonts.get(font_id).unwrap();
+                        match insert_text(replacement, cursor, &text, &state.line_infos, font) {
+                            Some((new_text, new_cursor, new_line_infos)) => {
+                                record_edit(state, Edit::Other, &text, cursor, history_limit);
+                                *text.to_mut() = new_text;
+                                cursor = new_cursor;
+                                state.update(|state| state.line_infos = new_line_infos);
+                            },
+                            None => (),
+                        }
+                    }
+                },
+
+                Some(Replace::All(replacement)) => {
+                    let replaced = query.replace_all(&text, replacement);
+                    let maybe_replaced = match maybe_mask {
+                        Some(mask) => mask::apply(mask, &replaced, 0).map(|(text, _)| text),
+                        None => Some(replaced),
+                    };
+                    if let Some(replaced) = maybe_replaced {
+                        let new_line_infos: Vec<_> = {
+                            let font = ui.fonts.get(font_id).unwrap();
+                            line_infos(&replaced, font, font_size, line_wrap, rect.w()).collect()
+                        };
+                        let height = text::height(new_line_infos.len(), font_size, line_spacing);
+                        let fits = height < rect.h() || !restrict_to_height;
+                        if replaced != *text && fits && is_accepted(&replaced) {
+                            record_edit(state, Edit::Other, &text, cursor, history_limit);
+                            *text.to_mut() = replaced;
+                            cursor = clamp_cursor(Cursor::Idx(match cursor {
+                                Cursor::Idx(idx) => idx,
+                                Cursor::Selection { start, end } => std::cmp::min(start, end),
+                            }), &new_line_infos);
+                            state.update(|state| state.line_infos = new_line_infos);
+                        }
+                    }
+                },
+
+                None => (),
+            }
+
+            // Replacing the selected match steps to the next match.
+            let maybe_step = match (maybe_find_step, maybe_replace) {
+                (Some(step), _) => Some(step),
+                (None, Some(Replace::Selection(_))) => Some(FindStep::Next),
+                _ => None,
+            };
+
+            if let Some(step) = maybe_step {
+                let matches = query.matches(&text);
+                let selected = cursor_char_range(cursor, &state.line_infos);
+                let maybe_target = match step {
+                    FindStep::Next => matches.iter()
+                        .map(|range| char_range(&text, range.clone()))
+                        .find(|range| range.start >= selected.end)
+                        .or_else(|| matches.first().map(|range| char_range(&text, range.clone()))),
+                    FindStep::Previous => matches.iter()
+                        .rev()
+                        .map(|range| char_range(&text, range.clone()))
+                        .find(|range| range.start < selected.start)
+                        .or_else(|| matches.last().map(|range| char_range(&text, range.clone()))),
+                };
+                if let Some(target) = maybe_target {
+                    let infos = state.line_infos.iter().cloned();
+                    let start = text::cursor::index_before_char(infos.clone(), target.start);
+                    let end = text::cursor::index_before_char(infos, target.end);
+                    if let (Some(start), Some(end)) = (start, end) {
+                        end_edit(state);
+                        cursor = Cursor::Selection { start: start, end: end };
+                    }
+                }
+            }
+        }
+
+        // Check for the following events:
+        // - `Text` events for receiving new text.
+        // - Left mouse `Press` events for either:
+        //     - setting the cursor or start of a selection.
+        //     - begin dragging selected text.
+        // - Left mouse `Drag` for extending the end of the selection, or for dragging selected text.
+        // - Key presses for editing commands (see `KeyBindings`).
+        //
+        // The events are collected up front so that the `Ui` may be mutated (i.e. to access the
+        // clipboard) while handling them.
+        let widget_events: Vec<event::Widget> = ui.widget_input(idx).events().collect();
+
+        // Stop the mouse and text events acted upon by the `TextEdit` from bubbling up to its
+        // parent. Key presses are marked as they are handled below.
+        for widget_event in &widget_events {
+            let is_handled = match *widget_event {
+                event::Widget::Press(ref press) => match press.button {
+                    event::Button::Mouse(input::MouseButton::Left, _) => true,
+                    _ => false,
+                },
+                event::Widget::Release(ref release) => match release.button {
+                    event::Button::Mouse(input::MouseButton::Left, _) => true,
+                    _ => false,
+                },
+                event::Widget::Click(ref click) => click.button == input::MouseButton::Left,
+                event::Widget::MultiClick(ref multi_click) =>
+                    multi_click.button == input::MouseButton::Left,
+                event::Widget::Drag(ref drag_event) =>
+                    drag_event.button == input::MouseButton::Left,
+                event::Widget::Text(ref text_event) =>
+                    !text_event.modifiers.contains(input::keyboard::CTRL)
+                    && !text_event.modifiers.contains(input::keyboard::GUI),
+                _ => false,
+            };
+            if is_handled {
+                ui.set_event_handled(idx, widget_event.clone());
+            }
+        }
+
+        'events: for widget_event in widget_events {
+            match widget_event {
+
+                event::Widget::Press(press) => match press.button {
+
+                    // If the left mouse button was pressed, place a `Cursor` with the starting
+                    // index at the mouse position.
+                    event::Button::Mouse(input::MouseButton::Left, rel_xy) => {
+                        let closest = {
+                            let abs_xy = utils::vec2_add(rel_xy, rect.xy());
+                            let infos = &state.line_infos;
+                            let font = ui.fonts.get(font_id).unwrap();
+                            closest_cursor_index_and_xy(abs_xy, &text, infos, font)
+                        };
+                        if let Some((closest_cursor, _)) = closest {
+                            cursor = Cursor::Idx(closest_cursor);
+                        }
+
+                        // TODO: Differentiate between Selecting and MoveSelection.
+                        drag = Some(Drag::Selecting);
+                        end_edit(state);
+                    }
+
+                    // Check for editing commands bound to the pressed key.
+                    event::Button::Keyboard(key) => {
+                        let command = bound_command(key, press.modifiers, maybe_key_bindings,
+                                                    tab_indents, &ui);
+                        let command = match command {
+                            Some(command) => command,
+                            None => continue 'events,
+                        };
+
+                        let (prev_text, prev_cursor) = (text.clone(), cursor);
+                        let mut is_clipboard_set = false;
+                        match command {
+
+                            // Move the cursor, deselecting any selected text.
+                            Command::Move(motion) => {
+                                let new_cursor_idx = {
+                                    let infos = &state.line_infos;
+                                    let font = ui.fonts.get(font_id).unwrap();
+                                    match cursor {
+                                        Cursor::Idx(cursor_idx) =>
+                                            move_cursor(motion, cursor_idx, &text, infos, font),
+                                        Cursor::Selection { start, end } => {
+                                            let min = std::cmp::min(start, end);
+                                            let max = std::cmp::max(start, end);
+                                            match motion {
+                                                // Move to the start/end of the current selection.
+                                                Motion::Left => min,
+                                                Motion::Right => max,
+                                                // Move by word from the start/end of the selection.
+                                                Motion::WordLeft =>
+                                                    move_cursor(motion, min, &text, infos, font),
+                                                Motion::WordRight =>
+                                                    move_cursor(motion, max, &text, infos, font),
+                                                _ => move_cursor(motion, end, &text, infos, font),
+                                            }
+                                        },
+                                    }
+                                };
+                                cursor = Cursor::Idx(new_cursor_idx);
+                                end_edit(state);
+                            },
+
+                            // Move the end of the selection, keeping the start anchored.
+                            Command::Select(motion) => {
+                                let (start, end) = match cursor {
+                                    Cursor::Idx(cursor_idx) => (cursor_idx, cursor_idx),
+                                    Cursor::Selection { start, end } => (start, end),
+                                };
+                                let new_end = {
+                                    let infos = &state.line_infos;
+                                    let font = ui.fonts.get(font_id).unwrap();
+                                    move_cursor(motion, end, &text, infos, font)
+                                };
+                                cursor = if new_end == start {
+                                    Cursor::Idx(start)
+                                } else {
+                                    Cursor::Selection { start: start, end: new_end }
+                                };
+                                end_edit(state);
+                            },
+
+                            Command::SelectAll => {
+                                let start = text::cursor::Index { line: 0, char: 0 };
+                                let end = {
+                                    let line_infos = state.line_infos.iter().cloned();
+                                    let shown = display_text(&text, maybe_password_char);
+                                    let char_count = shown.chars().count();
+                                    text::cursor::index_before_char(line_infos, char_count)
+                                        .expect("char index was out of range")
+                                };
+                                cursor = Cursor::Selection { start: start, end: end };
+                                end_edit(state);
+                            },
+
+                            // If `Cursor::Selection`, remove the selected text.
+                            // If `Cursor::Idx`, remove the text between the cursor and the
+                            // previous/next char or word.
+                            Command::DeleteBackward | Command::DeleteForward |
+                            Command::DeleteWordBackward | Command::DeleteWordForward => {
+                                let range = {
+                                    let line_infos = state.line_infos.iter().cloned();
+                                    match selected_char_range(cursor, line_infos.clone()) {
+                                        Some(range) => Some(range),
+                                        None => {
+                                            let cursor_idx = match cursor {
+                                                Cursor::Idx(idx) => idx,
+                                                Cursor::Selection { end, .. } => end,
+                                            };
+                                            let shown = display_text(&text, maybe_password_char);
+                                            let char_count = shown.chars().count();
+                                            let cursor_char_idx =
+                                                text::glyph::index_after_cursor(line_infos.clone(),
+                                                                                cursor_idx);
+                                            let target_char_idx = match command {
+                                                Command::DeleteBackward => cursor_char_idx
+                                                    .and_then(|i| if i > 0 { Some(i - 1) }
+                                                                  else     { None }),
+                                                Command::DeleteForward => cursor_char_idx
+                                                    .and_then(|i| if i < char_count { Some(i + 1) }
+                                                                  else              { None }),
+                                                _ => {
+                                                    let motion = match command {
+                                                        Command::DeleteWordBackward =>
+                                                            Motion::WordLeft,
+                                                        _ => Motion::WordRight,
+                                                    };
+                                                    let infos = &state.line_infos;
+                                                    let font = ui.fonts.get(font_id).unwrap();
+                                                    let target = move_cursor(motion, cursor_idx,
+                                                                             &text, infos, font);
+                                                    text::glyph::index_after_cursor(line_infos,
+                                                                                    target)
+                                                },
+                                            };
+                                            match (cursor_char_idx, target_char_idx) {
+                                                (Some(a), Some(b)) if a != b =>
+                                                    Some(std::cmp::min(a, b)..std::cmp::max(a, b)),
+                                                _ => None,
+                                            }
+                                        },
+                                    }
+                                };
+
+                                // Remove the range by replacing it with an empty string, so that
+                                // the result is formatted in the same manner as inserted text.
+                                let selection = range.and_then(|range| {
+                                    let line_infos = state.line_infos.iter().cloned();
+                                    let start = text::cursor::index_before_char(line_infos.clone(),
+                                                                                range.start);
+                                    let end = text::cursor::index_before_char(line_infos,
+                                                                              range.end);
+                                    match (start, end) {
+                                        (Some(start), Some(end)) =>
+                                            Some(Cursor::Selection { start: start, end: end }),
+                                        _ => None,
+                                    }
+                                });
+
+                                if let Some(selection) = selection {
+                                    let font = ui.fonts.get(font_id).unwrap();
+                                    match insert_text("", selection, &text, &state.line_infos,
+                                                      font) {
+                                        Some((new_text, new_cursor, new_line_infos)) => {
+                                            record_edit(state, Edit::Other, &text, cursor,
+                                                        history_limit);
+                                            *text.to_mut() = new_text;
+                                            cursor = new_cursor;
+                                            state.update(|state| {
+                                                state.line_infos = new_line_infos
+                                            });
+                                        }, _ => ()
+                                    }
+                                }
+                            },
+
+                            Command::Newline => {
+                                // Carry the indentation of the current line over to the new line.
+                                let mut newline = String::from("\n");
+                                if auto_indent {
+                                    let char_idx =
+                                        cursor_char_range(cursor, &state.line_infos).start;
+                                    newline.push_str(line_indent(&text, char_idx));
+                                }
+                                let font = ui.fonts.get(font_id).unwrap();
+                                match insert_text(&newline, cursor, &text, &state.line_infos,
+                                                  font) {
+                                    Some((new_text, new_cursor, new_line_infos)) => {
+                                        record_edit(state, Edit::Other, &text, cursor,
+                                                    history_limit);
+                                        *text.to_mut() = new_text;
+                                        cursor = new_cursor;
+                                        state.update(|state| state.line_infos = new_line_infos);
+                                    }, _ => ()
+                                }
+                            },
+
+                            // Indent or outdent each line touched by the selection. If nothing is
+                            // selected, `Indent` inserts an indent at the cursor.
+                            Command::Indent | Command::Outdent => {
+                                // Masked text has a fixed format that may not be indented.
+                                if maybe_mask.is_some() {
+                                    continue 'events;
+                                }
+
+                                let is_outdent = command == Command::Outdent;
+                                let (is_selection, is_reversed) = match cursor {
+                                    Cursor::Idx(_) => (false, false),
+                                    Cursor::Selection { start, end } => (true, end < start),
+                                };
+
+                                if !is_outdent && !is_selection {
+                                    let font = ui.fonts.get(font_id).unwrap();
+                                    match insert_text(indent, cursor, &text, &state.line_infos,
+                                                      font) {
+                                        Some((new_text, new_cursor, new_line_infos)) => {
+                                            record_edit(state, Edit::Other, &text, cursor,
+                                                        history_limit);
+                                            *text.to_mut() = new_text;
+                                            cursor = new_cursor;
+                                            state.update(|state| state.line_infos = new_line_infos);
+                                        }, _ => ()
+                                    }
+                                    continue 'events;
+                                }
+
+                                let selected = {
+                                    let shown = cursor_char_range(cursor, &state.line_infos);
+                                    let to_text =
+                                        |i| text_char_index(&text, maybe_password_char, i);
+                                    to_text(shown.start)..to_text(shown.end)
+                                };
+                                let (new_text, new_selected) =
+                                    indent_lines(&text, selected, indent, is_outdent);
+                                let new_selected = {
+                                    let to_shown =
+                                        |i| shown_char_index(&new_text, maybe_password_char, i);
+                                    to_shown(new_selected.start)..to_shown(new_selected.end)
+                                };
+                                if new_text == *text || !is_accepted(&new_text) {
+                                    continue 'events;
+                                }
+
+                                let new_line_infos: Vec<_> = {
+                                    let font = ui.fonts.get(font_id).unwrap();
+                                    let shown = display_text(&new_text, maybe_password_char);
+                                    line_infos(&shown, font, font_size, line_wrap, rect.w())
+                                        .collect()
+                                };
+                                let height = text::height(new_line_infos.len(), font_size,
+                                                          line_spacing);
+                                if height >= rect.h() && restrict_to_height {
+                                    continue 'events;
+                                }
+
+                                let new_cursor = {
+                                    let infos = new_line_infos.iter().cloned();
+                                    let start = text::cursor::index_before_char(infos.clone(),
+                                                                                new_selected.start);
+                                    let end = text::cursor::index_before_char(infos,
+                                                                              new_selected.end);
+                                    match (start, end) {
+                                        (Some(start), Some(end)) if is_selection && start != end =>
+                                            if is_reversed {
+                                                Cursor::Selection { start: end, end: start }
+                                            } else {
+                                                Cursor::Selection { start: start, end: end }
+                                            },
+                                        (_, Some(end)) => Cursor::Idx(end),
+                                        _ => cursor,
+                                    }
+                                };
+
+                                record_edit(state, Edit::Other, &text, cursor, history_limit);
+                                *text.to_mut() = new_text;
+                                cursor = clamp_cursor(new_cursor, &new_line_infos);
+                                state.update(|state| state.line_infos = new_line_infos);
+                            },
+
+                            Command::Undo | Command::Redo => {
+                                let is_redo = command == Command::Redo;
+                                let has_entry = if is_redo { !state.history.redos.is_empty() }
+                                                else       { !state.history.undos.is_empty() };
+                                if !has_entry {
+                                    continue 'events;
+                                }
+
+                                let mut restored = None;
+                                state.update(|state| {
+                                    restored = if is_redo { state.history.redo(&text, cursor) }
+                                               else       { state.history.undo(&text, cursor) };
+                                });
+
+                                if let Some(Snapshot { text: new_text, cursor: new_cursor }) = restored {
+                                    *text.to_mut() = new_text;
+                                    cursor = new_cursor;
+                                    state.update(|state| {
+                                        let font = ui.fonts.get(font_id).unwrap();
+                                        let w = rect.w();
+                                        let shown = display_text(&text, maybe_password_char);
+                                        state.line_infos =
+                                            line_infos(&shown, font, font_size, line_wrap, w)
+                                                .collect();
+                                    });
+                                }
+                            },
+
+                            Command::Copy | Command::Cut => {
+                                // Never let an obscured password out of the `TextEdit`.
+                                if maybe_password_char.is_some() {
+                                    continue 'events;
+                                }
+
+                                let selected = {
+                                    let line_infos = state.line_infos.iter().cloned();
+                                    selected_text(cursor, &text, line_infos)
+                                };
+                                let selected = match selected {
+                                    Some(selected) => selected,
+                                    None => continue 'events,
+                                };
+                                ui.set_clipboard_contents(selected);
+                                is_clipboard_set = true;
+
+                                if command == Command::Cut {
+                                    let font = ui.fonts.get(font_id).unwrap();
+                                    match insert_text("", cursor, &text, &state.line_infos, font) {
+                                        Some((new_text, new_cursor, new_line_infos)) => {
+                                            record_edit(state, Edit::Other, &text, cursor,
+                                                        history_limit);
+                                            *text.to_mut() = new_text;
+                                            cursor = new_cursor;
+                                            state.update(|state| {
+                                                state.line_infos = new_line_infos
+                                            });
+                                        }, _ => ()
+                                    }
+                                }
+                            },
+
+                            Command::Paste => {
+                                let contents = match ui.clipboard_contents() {
+                                    Some(contents) => contents,
+                                    None => continue 'events,
+                                };
+                                // If the contents would not fit (i.e. pasting multiple lines into
+                                // a single-line field) fall back to the first line.
+                                let font = ui.fonts.get(font_id).unwrap();
+                                let pasted =
+                                    insert_text(&contents, cursor, &text, &state.line_infos, font)
+                                        .or_else(|| contents.lines().next().and_then(|line| {
+                                            insert_text(line, cursor, &text, &state.line_infos,
+                                                        font)
+                                        }));
+                                match pasted {
+                                    Some((new_text, new_cursor, new_line_infos)) => {
+                                        record_edit(state, Edit::Other, &text, cursor,
+                                                    history_limit);
+                                        *text.to_mut() = new_text;
+                                        cursor = new_cursor;
+                                        state.update(|state| state.line_infos = new_line_infos);
+                                    }, _ => ()
+                                }
+                            },
+
+                        }
+
+                        // Commands that had no effect leave the press to the `TextEdit`'s
+                        // ancestors, e.g. for shortcuts scoped to a panel.
+                        if text != prev_text || cursor != prev_cursor || is_clipboard_set {
+                            ui.set_event_handled(idx, press.clone());
+                        }
+                    },
+
+                    _ => (),
+
+                },
+
+                event::Widget::Release(release) => {
+                    // Release drag.
+                    if let event::Button::Mouse(input::MouseButton::Left, _) = release.button {
+                        drag = None;
+                    }
+                },
+
+                // Select the word under the mouse on double-click, or its whole line on
+                // triple-click.
+                event::Widget::MultiClick(multi_click)
+                    if multi_click.button == input::MouseButton::Left =>
+                {
+                    let closest = {
+                        let abs_xy = utils::vec2_add(multi_click.xy, rect.xy());
+                        let infos = &state.line_infos;
+                        let font = ui.fonts.get(font_id).unwrap();
+                        closest_cursor_index_and_xy(abs_xy, &text, infos, font)
+                    };
+                    let cursor_idx = match closest {
+                        Some((cursor_idx, _)) => cursor_idx,
+                        None => continue 'events,
+                    };
+                    let maybe_selection = if multi_click.count == 2 {
+                        let shown = display_text(&text, maybe_password_char);
+                        word_at(cursor_idx, &shown, &state.line_infos)
+                    } else {
+                        line_at(cursor_idx, &state.line_infos)
+                    };
+                    if let Some((start, end)) = maybe_selection {
+                        cursor = if start == end { Cursor::Idx(start) }
+                                 else            { Cursor::Selection { start: start, end: end } };
+                        drag = None;
+                        end_edit(state);
+                    }
+                },
+
+                event::Widget::Text(event::Text { string, modifiers }) => {
+                    if modifiers.contains(input::keyboard::CTRL)
+                    || modifiers.contains(input::keyboard::GUI)
+                    || string.chars().count() == 0
+                    || string.chars().next().is_none() {
+                        continue 'events;
+                    }
+
+                    // Ignore text produced by arrow keys.
+                    // 
+                    // TODO: These just happened to be the modifiers for the arrows on OS X, I've
+                    // no idea if they also apply to other platforms. We should definitely see if
+                    // there's a better way to handle this, or whether this should be fixed
+                    // upstream.
+                    match &string[..] {
+                        "\u{f700}" | "\u{f701}" | "\u{f702}" | "\u{f703}" => continue 'events,
+                        _ => ()
+                    }
+
+                    // A tab that is bound to a command (i.e. `Indent`) is handled by its `Press`.
+                    if string == "\t" {
+                        let command = bound_command(input::Key::Tab, modifiers, maybe_key_bindings,
+                                                    tab_indents, &ui);
+                        if command.is_some() {
+                            continue 'events;
+                        }
+                    }
+
+                    let font = ui.fonts.get(font_id).unwrap();
+                    match insert_text(&string, cursor, &text, &state.line_infos, font) {
+                        Some((new_text, new_cursor, new_line_infos)) => {
+                            record_edit(state, Edit::Typing, &text, cursor, history_limit);
+                            *text.to_mut() = new_text;
+                            cursor = new_cursor;
+                            state.update(|state| state.line_infos = new_line_infos);
+                        }, _ => ()
+                    }
+                },
+
+                event::Widget::Composition(composition) => {
+                    let maybe_pre_edit = match composition {
+                        event::Composition::Start => Some(event::PreEdit {
+                            string: String::new(),
+                            cursor: None,
+                        }),
+                        event::Composition::Update(pre_edit) => Some(pre_edit),
+                        // The committed string is delivered separately as a `Text` event.
+                        event::Composition::Commit(_) => None,
+                    };
+                    if state.maybe_pre_edit != maybe_pre_edit {
+                        state.update(|state| state.maybe_pre_edit = maybe_pre_edit);
+                    }
+                },
+
+                event::Widget::UncapturesKeyboard => {
+                    if state.maybe_pre_edit.is_some() {
+                        state.update(|state| state.maybe_pre_edit = None);
+                    }
+                },
+
+                // Check whether or not we need to extend a text selection or drag some text.
+                event::Widget::Drag(drag_event) if drag_event.button == input::MouseButton::Left => {
+                    match drag {
+
+                        Some(Drag::Selecting) => {
+                            let start_cursor_idx = match cursor {
+                                Cursor::Idx(idx) => idx,
+                                Cursor::Selection { start, .. } => start,
+                            };
+                            let abs_xy = utils::vec2_add(drag_event.to, rect.xy());
+                            let infos = &state.line_infos;
+                            let font = ui.fonts.get(font_id).unwrap();
+                            match closest_cursor_index_and_xy(abs_xy, &text, infos, font) {
+                                Some((end_cursor_idx, _)) =>
+                                    cursor = Cursor::Selection {
+                                        start: start_cursor_idx,
+                                        end: end_cursor_idx,
+                                    },
+                                _ => (),
+                            }
+                        },
+
+                        // TODO: This should move the selected text.
+                        Some(Drag::MoveSelection) => {
+                            unimplemented!();
+                        },
+
+                        None => (),
+                    }
+                },
+
+                _ => (),
+            }
+        }
+
+        // While a selection is dragged beyond the top or bottom edge of the `TextEdit`, keep
+        // scrolling on each update, extending the selection as further lines come into view.
+        let mut is_drag_scrolling = false;
+        if !restrict_to_height && drag == Some(Drag::Selecting) {
+            let maybe_mouse_abs_xy = ui.widget_input(idx).mouse().map(|mouse| mouse.abs_xy());
+            if let Some(mouse_abs_xy) = maybe_mouse_abs_xy {
+                let scroll_y = if mouse_abs_xy[1] > rect.top() {
+                    rect.top() - mouse_abs_xy[1]
+                } else if mouse_abs_xy[1] < rect.bottom() {
+                    rect.bottom() - mouse_abs_xy[1]
+                } else {
+                    0.0
+                };
+                if scroll_y != 0.0 {
+                    let start_cursor_idx = match cursor {
+                        Cursor::Idx(idx) => idx,
+                        Cursor::Selection { start, .. } => start,
+                    };
+                    let closest = {
+                        let infos = &state.line_infos;
+                        let font = ui.fonts.get(font_id).unwrap();
+                        closest_cursor_index_and_xy(mouse_abs_xy, &text, infos, font)
+                    };
+                    if let Some((end_cursor_idx, _)) = closest {
+                        cursor = Cursor::Selection { start: start_cursor_idx, end: end_cursor_idx };
+                    }
+                    ui.scroll_widget(idx, [0.0, scroll_y]);
+                    is_drag_scrolling = true;
+                }
+            }
+        }
+
+        // Scroll the cursor into view if it has moved, e.g. while typing or moving via the
+        // keyboard.
+        if !restrict_to_height && !is_drag_scrolling && state.cursor != cursor {
+            let cursor_idx = match cursor {
+                Cursor::Idx(idx) => idx,
+                Cursor::Selection { end, .. } => end,
+            };
+            let maybe_cursor_y = {
+                let font = ui.fonts.get(font_id).unwrap();
+                cursor_xy_at(cursor_idx, &text, &state.line_infos, font).map(|(_, y)| y)
+            };
+            if let Some(cursor_y) = maybe_cursor_y {
+                let scroll_y = if cursor_y.end > rect.top() {
+                    rect.top() - cursor_y.end
+                } else if cursor_y.start < rect.bottom() {
+                    rect.bottom() - cursor_y.start
+                } else {
+                    0.0
+                };
+                ui.scroll_widget(idx, [0.0, scroll_y]);
+            }
+        }
+
+        if state.cursor != cursor {
+            state.update(|state| state.cursor = cursor);
+        }
+
+        if state.drag != drag {
+            state.update(|state| state.drag = drag);
+        }
+
+        // Takes the `String` from the `Cow` if the `Cow` is `Owned`.
+        fn take_if_owned(text: std::borrow::Cow<str>) -> Option<String> {
+            match text {
+                std::borrow::Cow::Borrowed(_) => None,
+                std::borrow::Cow::Owned(s) => Some(s),
+            }
+        }
+
+        let color = style.color(ui.theme());
+        let font_size = style.font_size(ui.theme());
+        let is_capturing_keyboard =
+            ui.global_input().current.widget_capturing_keyboard == Some(idx);
+
+        // Keep `Tab` from moving keyboard focus away while it is bound to a command, or while the
+        // `TextEdit` accepts tabs.
+        if is_capturing_keyboard {
+            use input::keyboard::{NO_MODIFIER, SHIFT};
+            let bound_tab_modifiers: Vec<_> = [NO_MODIFIER, SHIFT].iter()
+                .cloned()
+                .filter(|&modifiers| {
+                    bound_command(input::Key::Tab, modifiers, maybe_key_bindings, tab_indents, &ui)
+                        .is_some()
+                })
+                .collect();
+            if !bound_tab_modifiers.is_empty() || accepts_tab {
+                ui.consume_tab(idx);
+            }
+
+            // Keep the keys used to enter text and to trigger editing commands from triggering
+            // any of the `Ui`'s shortcuts, including `Tab` while it indents via `tab_indents`.
+            ui.consume_text_input(idx);
+            ui.consume_bound_keys(idx, maybe_key_bindings);
+            for modifiers in bound_tab_modifiers {
+                ui.consume_key(idx, modifiers, input::Key::Tab);
+            }
+        }
+
+        // Text may be selected by pressing anywhere within the widget.
+        ui.set_mouse_cursor(idx, MouseCursor::Text);
+
+        // While the input method is composing, its pre-edit text is shown at the cursor in place
+        // of any selected text.
+        let maybe_composed = match state.maybe_pre_edit {
+            Some(ref pre_edit) if is_capturing_keyboard
+                               && maybe_password_char.is_none()
+                               && !pre_edit.string.is_empty() => {
+                let insert_idx = match cursor {
+                    Cursor::Idx(idx) => idx,
+                    Cursor::Selection { start, end } => std::cmp::min(start, end),
+                };
+                let line_infos = state.line_infos.iter().cloned();
+                text::glyph::index_after_cursor(line_infos, insert_idx).map(|char_idx| {
+                    let font = ui.fonts.get(font_id).unwrap();
+                    compose(&text, char_idx, pre_edit, font, font_size, line_wrap, rect.w())
+                })
+            },
+            _ => None,
+        };
+
+        let text_rect = match maybe_composed {
+            Some(ref composed) => text_area(&composed.line_infos),
+            None => text_area(&state.line_infos),
+        };
+
+        // The `TextEdit`'s scrollable range is determined by the bounding box of its non-graphic
+        // kids, so instantiate an invisible one spanning the text. The scroll offset is applied to
+        // this widget's position by the `Ui`, so it is given the un-scrolled position here.
+        if !restrict_to_height {
+            let scroll_area_idx = state.scroll_area_idx.get(&mut ui);
+            widget::Rectangle::fill([0.0, text_rect.h()])
+                .x_y(text_rect.left(), text_rect.y() - y_scroll_offset)
+                .color(color::TRANSPARENT)
+                .parent(idx)
+                .set(scroll_area_idx, &mut ui);
+        }
+
+        match maybe_highlighter {
+
+            // Draw each run of uniformly styled text with its own `Text` widget.
+            Some(ref highlighter) if maybe_password_char.is_none() && maybe_composed.is_none() => {
+                let spans = highlighter(&text);
+                let runs = {
+                    let font = ui.fonts.get(font_id).unwrap();
+                    let area = text_area(&state.line_infos);
+                    let xys_per_line = text::cursor::xys_per_line_from_text(&text,
+                                                                            &state.line_infos,
+                                                                            font, font_size,
+                                                                            x_align, y_align,
+                                                                            line_spacing, area);
+                    let xs_per_line = xys_per_line.map(|(xs, y)| (xs.collect::<Vec<_>>(), y));
+                    highlighted_runs(&text, &state.line_infos, xs_per_line, &spans)
+                };
+
+                for (i, run) in runs.into_iter().enumerate() {
+                    if i == state.span_text_indices.len() {
+                        state.update(|state| {
+                            state.span_text_indices.push(ui.new_unique_node_index());
+                        });
+                    }
+                    let span_text_idx = state.span_text_indices[i];
+                    let HighlightedRun { range, rect: run_rect, maybe_style } = run;
+                    let (run_color, run_font_id) = match maybe_style {
+                        Some((color, maybe_font_id)) => {
+                            let font_id = maybe_font_id
+                                .and_then(|id| ui.fonts.get(id).map(|_| id))
+                                .unwrap_or(font_id);
+                            (color, font_id)
+                        },
+                        None => (color, font_id),
+                    };
+                    widget::Text::new(&text[range])
+                        .no_line_wrap()
+                        .wh(run_rect.dim())
+                        .xy(run_rect.xy())
+                        .align_text_left()
+                        .graphics_for(idx)
+                        .color(run_color)
+                        .font_size(font_size)
+                        .font_id(run_font_id)
+                        .set(span_text_idx, &mut ui);
+                }
+            },
+
+            _ => {
+                let shown = match maybe_composed {
+                    Some(ref composed) => std::borrow::Cow::Borrowed(&composed.text[..]),
+                    None => display_text(&text, maybe_password_char),
+                };
+                match line_wrap {
+                    Wrap::Whitespace => widget::Text::new(&shown).wrap_by_word(),
+                    Wrap::Character => widget::Text::new(&shown).wrap_by_character(),
+                }
+                    .wh(text_rect.dim())
+                    .xy(text_rect.xy())
+                    .align_text_to(x_align)
+                    .graphics_for(idx)
+                    .color(color)
+                    .line_spacing(line_spacing)
+                    .font_size(font_size)
+                    .font_id(font_id)
+                    .set(text_idx, &mut ui);
+            },
+
+        }
+
+        // Draw the placeholder text if the text is empty and the user is not editing it.
+        if let Some(placeholder) = maybe_placeholder {
+            if text.is_empty() && !is_capturing_keyboard {
+                let placeholder_color = style.placeholder_color(ui.theme())
+                    .unwrap_or_else(|| color.alpha(0.5));
+                let placeholder_font_id = style.placeholder_font_id(ui.theme())
+                    .and_then(|id| ui.fonts.get(id).map(|_| id))
+                    .unwrap_or(font_id);
+                let placeholder_rect = {
+                    let font = ui.fonts.get(placeholder_font_id).unwrap();
+                    let num_lines = line_infos(placeholder, font, font_size, line_wrap, rect.w())
+                        .count();
+                    let height = text::height(num_lines, font_size, line_spacing);
+                    let y = Range::new(0.0, height).align_to(y_align, rect.y);
+                    Rect { x: rect.x, y: y }
+                };
+                let placeholder_idx = state.placeholder_idx.get(&mut ui);
+                match line_wrap {
+                    Wrap::Whitespace => widget::Text::new(placeholder).wrap_by_word(),
+                    Wrap::Character => widget::Text::new(placeholder).wrap_by_character(),
+                }
+                    .wh(placeholder_rect.dim())
+                    .xy(placeholder_rect.xy())
+                    .align_text_to(x_align)
+                    .graphics_for(idx)
+                    .color(placeholder_color)
+                    .line_spacing(line_spacing)
+                    .font_size(font_size)
+                    .font_id(placeholder_font_id)
+                    .set(placeholder_idx, &mut ui);
+            }
+        }
+
+        // Highlight each match for the `find` query, emphasising the selected match.
+        if let Some(query) = maybe_query {
+            if maybe_password_char.is_none() && maybe_composed.is_none() {
+                let match_rects: Vec<(Rect, bool)> = {
+                    let font = ui.fonts.get(font_id).unwrap();
+                    let area = text_area(&state.line_infos);
+                    let selected = cursor_char_range(cursor, &state.line_infos);
+                    let mut match_rects = Vec::new();
+                    for range in query.matches(&text) {
+                        let range = char_range(&text, range);
+                        let is_selected = range == selected;
+                        let infos = state.line_infos.iter().cloned();
+                        let start = text::cursor::index_before_char(infos.clone(), range.start);
+                        let end = text::cursor::index_before_char(infos.clone(), range.end);
+                        if let (Some(start), Some(end)) = (start, end) {
+                            let lines = state.line_infos.iter()
+                                .map(|info| &text[info.byte_range()]);
+                            let line_rects = text::line::rects(infos, font_size, area,
+                                                               x_align, y_align, line_spacing);
+                            let lines_with_rects = lines.zip(line_rects);
+                            let rects = text::line::selected_rects(lines_with_rects, font,
+                                                                   font_size, start, end);
+                            match_rects.extend(rects.map(|rect| (rect, is_selected)));
+                        }
+                    }
+                    match_rects
+                };
+
+                let match_color = style.match_color(ui.theme());
+                for (i, &(match_rect, is_selected)) in match_rects.iter().enumerate() {
+                    if i == state.match_rectangle_indices.len() {
+                        state.update(|state| {
+                            state.match_rectangle_indices.push(ui.new_unique_node_index());
+                        });
+                    }
+                    let match_rectangle_idx = state.match_rectangle_indices[i];
+                    let alpha = if is_selected { 0.5 } else { 0.25 };
+                    widget::Rectangle::fill(match_rect.dim())
+                        .xy(match_rect.xy())
+                        .color(match_color.alpha(alpha))
+                        .graphics_for(idx)
+                        .parent(idx)
+                        .set(match_rectangle_idx, &mut ui);
+                }
+            }
+        }
+
+        // Draw the line for the cursor.
+        let cursor_idx = match cursor {
+            Cursor::Idx(idx) => idx,
+            Cursor::Selection { end, .. } => end,
+        };
+
+        // If this widget is not capturing the keyboard, no need to draw cursor or selection.
+        if !is_capturing_keyboard {
+            return take_if_owned(text);
+        }
+
+        let (cursor_x, cursor_y_range) = {
+            let font = ui.fonts.get(font_id).unwrap();
+            let maybe_xy = match maybe_composed {
+                Some(ref composed) => cursor_xy_at(composed.cursor, &composed.text,
+                                                   &composed.line_infos, font),
+                None => cursor_xy_at(cursor_idx, &text, &state.line_infos, font),
+            };
+            maybe_xy
+                .unwrap_or_else(|| {
+                    let x = rect.left();
+                    let y = Range::new(0.0, font_size as Scalar).align_to(y_align, rect.y);
+                    (x, y)
+                })
+        };
+
+        let cursor_line_idx = state.cursor_idx.get(&mut ui);
+        let start = [0.0, cursor_y_range.start];
+        let end = [0.0, cursor_y_range.end];
+        widget::Line::centred(start, end)
+            .x_y(cursor_x, cursor_y_range.middle())
+            .graphics_for(idx)
+            .parent(idx)
+            .color(color)
+            .set(cursor_line_idx, &mut ui);
+
+        // Report the caret so that the backend may position the input method's candidate window.
+        ui.set_caret_rect(Rect { x: Range::new(cursor_x, cursor_x), y: cursor_y_range });
+
+        // Underline the pre-edit text and skip drawing the selection that it replaces.
+        if let Some(ref composed) = maybe_composed {
+            let underlines: Vec<Rect> = {
+                let line_infos = composed.line_infos.iter().cloned();
+                let lines = line_infos.clone().map(|info| &composed.text[info.byte_range()]);
+                let area = text_area(&composed.line_infos);
+                let line_rects = text::line::rects(line_infos.clone(), font_size, area,
+                                                   x_align, y_align, line_spacing);
+                let lines_with_rects = lines.zip(line_rects.clone());
+                let font = ui.fonts.get(font_id).unwrap();
+                let (start, end) = composed.pre_edit;
+                text::line::selected_rects(lines_with_rects, font, font_size, start, end).collect()
+            };
+
+            for (i, underline) in underlines.iter().enumerate() {
+                if i == state.pre_edit_underline_indices.len() {
+                    state.update(|state| {
+                        state.pre_edit_underline_indices.push(ui.new_unique_node_index());
+                    });
+                }
+                let underline_idx = state.pre_edit_underline_indices[i];
+                let start = [underline.left(), underline.bottom()];
+                let end = [underline.right(), underline.bottom()];
+                widget::Line::abs(start, end)
+                    .graphics_for(idx)
+                    .parent(idx)
+                    .color(color)
+                    .set(underline_idx, &mut ui);
+            }
+
+            return take_if_owned(text);
+        }
+
+        if let Cursor::Selection { start, end } = cursor {
+            let (start, end) = (std::cmp::min(start, end), std::cmp::max(start, end));
+
+            let selected_rects: Vec<Rect> = {
+                let shown = display_text(&text, maybe_password_char);
+                let line_infos = state.line_infos.iter().cloned();
+                let lines = line_infos.clone().map(|info| &shown[info.byte_range()]);
+                let area = text_area(&state.line_infos);
+                let line_rects = text::line::rects(line_infos.clone(), font_size, area,
+                                                   x_align, y_align, line_spacing);
+                let lines_with_rects = lines.zip(line_rects.clone());
+                let font = ui.fonts.get(font_id).unwrap();
+                text::line::selected_rects(lines_with_rects, font, font_size, start, end).collect()
+            };
+
+            // Draw a semi-transparent `Rectangle` for the selected range across each line.
+            let selected_rect_color = color.highlighted().alpha(0.25);
+            for (i, selected_rect) in selected_rects.iter().enumerate() {
+                if i == state.selected_rectangle_indices.len() {
+                    state.update(|state| {
+                        state.selected_rectangle_indices.push(ui.new_unique_node_index());
+                    });
+                }
+                let selected_rectangle_idx = state.selected_rectangle_indices[i];
+
+                widget::Rectangle::fill(selected_rect.dim())
+                    .xy(selected_rect.xy())
+                    .color(selected_rect_color)
+                    .graphics_for(idx)
+                    .parent(idx)
+                    .set(selected_rectangle_idx, &mut ui);
+            }
+        }
+
+        take_if_owned(text)
+    }
+
+}
+
+/// The text as it is displayed while the input method is composing.
+struct Composed {
+    /// The text with the pre-edit string inserted.
+    text: String,
+    /// Information about each line of the composed `text`.
+    line_infos: Vec<text::line::Info>,
+    /// The start and end of the pre-edit string within the composed `text`.
+    pre_edit: (text::cursor::Index, text::cursor::Index),
+    /// The position of the input method's cursor within the composed `text`.
+    cursor: text::cursor::Index,
+}
+
+/// Insert the given pre-edit string into the `text` at the given char index.
+fn compose(text: &str,
+           char_idx: usize,
+           pre_edit: &event::PreEdit,
+           font: &text::Font,
+           font_size: FontSize,
+           line_wrap: Wrap,
+           max_width: Scalar) -> Composed
+{
+    let byte_idx = text.char_indices().nth(char_idx).map(|(i, _)| i).unwrap_or(text.len());
+    let mut composed = String::with_capacity(text.len() + pre_edit.string.len());
+    composed.push_str(&text[..byte_idx]);
+    composed.push_str(&pre_edit.string);
+    composed.push_str(&text[byte_idx..]);
+
+    let infos = text::line::infos(&composed, font, font_size);
+    let line_infos: Vec<_> = match line_wrap {
+        Wrap::Whitespace => infos.wrap_by_whitespace(max_width).collect(),
+        Wrap::Character => infos.wrap_by_character(max_width).collect(),
+    };
+
+    let pre_edit_len = pre_edit.string.chars().count();
+    let pre_edit_cursor = pre_edit.cursor.map_or(pre_edit_len, |c| std::cmp::min(c, pre_edit_len));
+    let (start, end, cursor) = {
+        let index_before_char = |char_idx: usize| {
+            text::cursor::index_before_char(line_infos.iter().cloned(), char_idx)
+                .unwrap_or(text::cursor::Index { line: 0, char: 0 })
+        };
+        (index_before_char(char_idx),
+         index_before_char(char_idx + pre_edit_len),
+         index_before_char(char_idx + pre_edit_cursor))
+    };
+
+    Composed {
+        text: composed,
+        line_infos: line_infos,
+        pre_edit: (start, end),
+        cursor: cursor,
+    }
+}
+
+/// A run of text within a single line to be drawn with a single style.
+struct HighlightedRun {
+    /// The range of bytes within the text.
+    range: std::ops::Range<usize>,
+    /// The area occupied by the run.
+    rect: Rect,
+    /// The color and font given by the highlighter, or `None` for the default style.
+    maybe_style: Option<(Color, Option<text::font::Id>)>,
+}
+
+/// Split each line of the given text into runs of uniformly styled text.
+///
+/// `xs_per_line` yields every cursor position along the *x* axis and the *y* range for each line.
+fn highlighted_runs<I>(text: &str,
+                       line_infos: &[text::line::Info],
+                       xs_per_line: I,
+                       spans: &[Span]) -> Vec<HighlightedRun>
+    where I: Iterator<Item=(Vec<Scalar>, Range)>,
+{
+    let spans: Vec<&Span> = spans.iter()
+        .filter(|span| span.range.start < span.range.end
+                    && span.range.end <= text.len()
+                    && text.is_char_boundary(span.range.start)
+                    && text.is_char_boundary(span.range.end))
+        .collect();
+
+    let mut runs = Vec::new();
+    for (info, (xs, y)) in line_infos.iter().zip(xs_per_line) {
+        let line = info.byte_range();
+
+        // Every byte index within the line at which the style may change.
+        let mut bounds = vec![line.start, line.end];
+        for span in &spans {
+            for &b in &[span.range.start, span.range.end] {
+                if line.start < b && b < line.end {
+                    bounds.push(b);
+                }
+            }
+        }
+        bounds.sort();
+        bounds.dedup();
+
+        for window in bounds.windows(2) {
+            let (start, end) = (window[0], window[1]);
+            if start == end {
+                continue;
+            }
+            let start_char = text[line.start..start].chars().count();
+            let end_char = start_char + text[start..end].chars().count();
+            let (x_start, x_end) = match (xs.get(start_char), xs.get(end_char)) {
+                (Some(&x_start), Some(&x_end)) => (x_start, x_end),
+                _ => continue,
+            };
+            let maybe_style = spans.iter()
+                .rev()
+                .find(|span| span.range.start <= start && end <= span.range.end)
+                .map(|span| (span.color, span.font_id));
+            runs.push(HighlightedRun {
+                range: start..end,
+                rect: Rect { x: Range::new(x_start, x_end), y: y },
+                maybe_style: maybe_style,
+            });
+        }
+    }
+    runs
+}
+
+/// The range of chars that are selected by the given cursor.
+///
+/// If there is no selection, the range is empty and positioned at the cursor.
+fn cursor_char_range(cursor: Cursor, line_infos: &[text::line::Info]) -> std::ops::Range<usize> {
+    let infos = line_infos.iter().cloned();
+    match selected_char_range(cursor, infos.clone()) {
+        Some(range) => range,
+        None => {
+            let idx = match cursor {
+                Cursor::Idx(idx) => idx,
+                Cursor::Selection { end, .. } => end,
+            };
+            let char_idx = text::glyph::index_after_cursor(infos, idx).unwrap_or(0);
+            char_idx..char_idx
+        },
+    }
+}
+
+/// The cursor indices at the start and end of the word at the given cursor index.
+///
+/// Words are bounded as they are by `Index::previous_word_start` and `Index::next_word_end`. An
+/// index that falls between a word and whitespace selects the word. Returns `None` if the index is
+/// surrounded by whitespace.
+fn word_at(idx: text::cursor::Index,
+           text: &str,
+           line_infos: &[text::line::Info]) -> Option<(text::cursor::Index, text::cursor::Index)>
+{
+    let line = match line_infos.get(idx.line) {
+        Some(info) => &text[info.byte_range()],
+        None => return None,
+    };
+    let is_word_char = |i: usize| line.chars().nth(i).map_or(false, |c| !c.is_whitespace());
+    let is_word_after = is_word_char(idx.char);
+    let is_word_before = idx.char > 0 && is_word_char(idx.char - 1);
+
+    let infos = line_infos.iter().cloned();
+    let start = if is_word_before { idx.previous_word_start(text, infos.clone()) }
+                else              { Some(idx) };
+    let end = if is_word_after { idx.next_word_end(text, infos) }
+              else             { Some(idx) };
+    match (start, end) {
+        (Some(start), Some(end)) if is_word_before || is_word_after => Some((start, end)),
+        _ => None,
+    }
+}
+
+/// The cursor indices at the start and end of the line of text containing the given cursor index.
+///
+/// A line that has been wrapped over multiple lines is selected in full. The newline that ends
+/// the line is not included.
+fn line_at(idx: text::cursor::Index,
+           line_infos: &[text::line::Info]) -> Option<(text::cursor::Index, text::cursor::Index)>
+{
+    if idx.line >= line_infos.len() {
+        return None;
+    }
+    let is_newline = |i: usize| match line_infos[i].end_break {
+        text::line::Break::Newline { .. } => true,
+        _ => false,
+    };
+    let first = (0..idx.line).rev().find(|&i| is_newline(i)).map_or(0, |i| i + 1);
+    let last = (idx.line..line_infos.len()).find(|&i| is_newline(i))
+        .unwrap_or(line_infos.len() - 1);
+    let last_info = &line_infos[last];
+    let start = text::cursor::Index { line: first, char: 0 };
+    let end = text::cursor::Index { line: last, char: last_info.end_char() - last_info.start_char };
+    Some((start, end))
+}
+
+/// The command bound to the given key and modifiers.
+///
+/// The given `KeyBindings` take precedence over the `Ui`'s (see `Ui::key_bindings`). If
+/// `tab_indents` is `true` and the bindings do not bind `Tab`, `Tab` and `Shift+Tab` are bound to
+/// `Indent` and `Outdent`.
+fn bound_command(key: input::Key,
+                 modifiers: input::keyboard::ModifierKey,
+                 maybe_key_bindings: Option<&KeyBindings>,
+                 tab_indents: bool,
+                 ui: &Ui) -> Option<Command>
+{
+    use input::keyboard::{NO_MODIFIER, SHIFT};
+    let key_bindings = maybe_key_bindings.unwrap_or_else(|| ui.key_bindings());
+    key_bindings.command(key, modifiers).or_else(|| {
+        let is_tab_unbound = key_bindings.command(input::Key::Tab, NO_MODIFIER).is_none();
+        if key != input::Key::Tab || !tab_indents || !is_tab_unbound {
+            return None;
+        }
+        if modifiers == NO_MODIFIER {
+            Some(Command::Indent)
+        } else if modifiers == SHIFT {
+            Some(Command::Outdent)
+        } else {
+            None
+        }
+    })
+}
+
+/// The whitespace at the start of the line containing the given char index, up to the index.
+fn line_indent(text: &str, char_idx: usize) -> &str {
+    let byte_idx = text.char_indices().nth(char_idx).map(|(i, _)| i).unwrap_or(text.len());
+    let line_start = text[..byte_idx].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line = &text[line_start..byte_idx];
+    let indent_len = line.len() - line.trim_left_matches(|c| c == ' ' || c == '\t').len();
+    &line[..indent_len]
+}
+
+/// Indent (or outdent) each line touched by the given range of chars.
+///
+/// A non-empty range that ends at the start of a line does not touch that line. Outdenting
+/// removes the `indent` from the start of a line, or otherwise a single leading tab or as many
+/// leading spaces as there are chars in the `indent`.
+///
+/// Returns the new text along with the given range adjusted for the inserted or removed chars.
+fn indent_lines(text: &str,
+                chars: std::ops::Range<usize>,
+                indent: &str,
+                outdent: bool) -> (String, std::ops::Range<usize>)
+{
+    let indent_len = indent.chars().count();
+    let ends_at_line_start = chars.end > chars.start
+        && text.chars().nth(chars.end - 1) == Some('\n');
+    let last = if ends_at_line_start { chars.end - 1 } else { chars.end };
+
+    let mut new_text = String::with_capacity(text.len());
+    let (mut start, mut end) = (chars.start, chars.end);
+    let mut line_start = 0;
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            new_text.push('\n');
+        }
+        let line_len = line.chars().count();
+        let is_touched = line_start <= last && line_start + line_len >= chars.start;
+        if !is_touched {
+            new_text.push_str(line);
+        } else if !outdent {
+            new_text.push_str(indent);
+            new_text.push_str(line);
+            if chars.start > line_start {
+                start += indent_len;
+            }
+            if chars.end >= line_start {
+                end += indent_len;
+            }
+        } else {
+            // The number of chars and bytes to remove. Tabs and spaces are a single byte.
+            let (removed_chars, removed_bytes) = if !indent.is_empty() && line.starts_with(indent) {
+                (indent_len, indent.len())
+            } else if line.starts_with('\t') {
+                (1, 1)
+            } else {
+                let max = std::cmp::max(indent_len, 1);
+                let spaces = line.chars().take_while(|&c| c == ' ').take(max).count();
+                (spaces, spaces)
+            };
+            new_text.push_str(&line[removed_bytes..]);
+            if chars.start >= line_start {
+                start -= std::cmp::min(chars.start - line_start, removed_chars);
+            }
+            if chars.end >= line_start {
+                end -= std::cmp::min(chars.end - line_start, removed_chars);
+            }
+        }
+        line_start += line_len + 1;
+    }
+
+    (new_text, start..end)
+}
+
+/// Convert the given range of bytes within the text to a range of chars.
+fn char_range(text: &str, bytes: std::ops::Range<usize>) -> std::ops::Range<usize> {
+    let start = text[..bytes.start].chars().count();
+    let end = start + text[bytes].chars().count();
+    start..end
+}
+
+/// The text as it is displayed, with each grapheme replaced by the `password_char` if there is
+/// one.
+///
+/// Line breaks are preserved so that the obscured text is laid out over the same lines. As each
+/// grapheme is replaced by a single char, the cursor indices, which always refer to the displayed
+/// text, must be converted via `text_char_index` before indexing into the real text.
+fn display_text(text: &str, maybe_password_char: Option<char>) -> std::borrow::Cow<str> {
+    match maybe_password_char {
+        None => std::borrow::Cow::Borrowed(text),
+        Some(password_char) => {
+            let obscure = |g: &str| if g.contains('\n') { '\n' } else { password_char };
+            std::borrow::Cow::Owned(text.graphemes(true).map(obscure).collect())
+        },
+    }
+}
+
+/// Convert the given char index into the displayed text to a char index into the real `text`.
+fn text_char_index(text: &str,
+                   maybe_password_char: Option<char>,
+                   shown_char_idx: usize) -> usize
+{
+    match maybe_password_char {
+        None => shown_char_idx,
+        Some(_) => text.graphemes(true)
+            .take(shown_char_idx)
+            .map(|g| g.chars().count())
+            .sum(),
+    }
+}
+
+/// Convert the given char index into the real `text` to a char index into the displayed text.
+///
+/// An index that falls within a grapheme is rounded down to the start of that grapheme.
+fn shown_char_index(text: &str,
+                    maybe_password_char: Option<char>,
+                    text_char_idx: usize) -> usize
+{
+    match maybe_password_char {
+        None => text_char_idx,
+        Some(_) => {
+            let mut char_count = 0;
+            text.graphemes(true)
+                .take_while(|g| {
+                    char_count += g.chars().count();
+                    char_count <= text_char_idx
+                })
+                .count()
+        },
+    }
+}
+
+/// Clamp the given `cursor` to the positions available within the given `line_infos`.
+fn clamp_cursor(cursor: Cursor, line_infos: &[text::line::Info]) -> Cursor {
+    let clamp = |idx: text::cursor::Index| -> text::cursor::Index {
+        match line_infos.get(idx.line) {
+            Some(info) => {
+                let char = std::cmp::min(idx.char, info.end_char() - info.start_char);
+                text::cursor::Index { line: idx.line, char: char }
+            },
+            None => match line_infos.last() {
+                Some(info) => text::cursor::Index {
+                    line: line_infos.len() - 1,
+                    char: info.end_char() - info.start_char,
+                },
+                None => text::cursor::Index { line: 0, char: 0 },
+            },
+        }
+    };
+    match cursor {
+        Cursor::Idx(idx) => Cursor::Idx(clamp(idx)),
+        Cursor::Selection { start, end } => {
+            let (start, end) = (clamp(start), clamp(end));
+            if start == end { Cursor::Idx(start) }
+            else            { Cursor::Selection { start: start, end: end } }
+        },
+    }
+}
+
+/// The range of `char` indices covered by the given `cursor`, if it is a non-empty selection.
+fn selected_char_range<I>(cursor: Cursor, line_infos: I) -> Option<std::ops::Range<usize>>
+    where I: Iterator<Item=text::line::Info> + Clone,
+{
+    match cursor {
+        Cursor::Idx(_) => None,
+        Cursor::Selection { start, end } => {
+            let (start, end) = (std::cmp::min(start, end), std::cmp::max(start, end));
+            let start_idx = text::glyph::index_after_cursor(line_infos.clone(), start);
+            let end_idx = text::glyph::index_after_cursor(line_infos, end);
+            match (start_idx, end_idx) {
+                (Some(start_idx), Some(end_idx)) if start_idx < end_idx =>
+                    Some(start_idx..end_idx),
+                _ => None,
+            }
+        },
+    }
+}
+
+/// The text covered by the given `cursor`, if it is a non-empty selection.
+fn selected_text<I>(cursor: Cursor, text: &str, line_infos: I) -> Option<String>
+    where I: Iterator<Item=text::line::Info> + Clone,
+{
+    selected_char_range(cursor, line_infos).map(|range| {
+        text.chars().skip(range.start).take(range.end - range.start).collect()
+    })
+}
+
+/// Record the `text` and `cursor` as they were prior to the given `edit` within the history.
+fn record_edit(state: &mut widget::State<State>,
+               edit: Edit,
+               text: &str,
+               cursor: Cursor,
+               limit: usize)
+{
+    state.update(|state| state.history.record(edit, text, cursor, limit));
+}
+
+/// Ensure the next edit starts a new history entry, i.e. after the cursor has been moved.
+fn end_edit(state: &mut widget::State<State>) {
+    if state.history.last_edit.is_some() {
+        state.update(|state| state.history.end_edit());
+    }
+}
+
+
+impl<'a> Colorable for TextEdit<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+
+#[test]
+fn history_coalesces_consecutive_typing() {
+    let idx = |char| Cursor::Idx(text::cursor::Index { line: 0, char: char });
+    let mut history = History::new();
+    history.record(Edit::Typing, "", idx(0), 128);
+    history.record(Edit::Typing, "a", idx(1), 128);
+    history.record(Edit::Typing, "ab", idx(2), 128);
+    assert_eq!(history.undos.len(), 1);
+
+    // Moving the cursor ends the run of typing.
+    history.end_edit();
+    history.record(Edit::Typing, "abc", idx(3), 128);
+    assert_eq!(history.undos.len(), 2);
+
+    let snapshot = history.undo("abcd", idx(4)).unwrap();
+    assert_eq!(snapshot, Snapshot { text: "abc".to_owned(), cursor: idx(3) });
+    let snapshot = history.redo("abc", idx(3)).unwrap();
+    assert_eq!(snapshot, Snapshot { text: "abcd".to_owned(), cursor: idx(4) });
+
+    history.record(Edit::Other, "abcd", idx(4), 2);
+    assert_eq!(history.undos.len(), 2);
+    assert!(history.redos.is_empty());
+}
+
+#[test]
+fn indent_lines_touches_each_selected_line() {
+    let text = "foo\nbar\nbaz";
+    assert_eq!(indent_lines(text, 1..5, "\t", false), ("\tfoo\n\tbar\nbaz".to_owned(), 2..7));
+    // A selection that ends at the start of a line does not touch that line.
+    assert_eq!(indent_lines(text, 0..4, "\t", false), ("\tfoo\nbar\nbaz".to_owned(), 0..5));
+    assert_eq!(indent_lines("\tfoo\n  bar", 2..8, "  ", true), ("foo\nbar".to_owned(), 1..5));
+    assert_eq!(indent_lines("foo", 1..1, "\t", true), ("foo".to_owned(), 1..1));
+    assert_eq!(line_indent("foo\n\t  bar", 9), "\t  ");
+    assert_eq!(line_indent("  foo", 1), " ");
+}
+
+#[test]
+fn password_text_is_obscured_per_grapheme() {
+    // "e" followed by a combining acute accent is a single grapheme of two chars.
+    let text = "e\u{301}x\r\ny";
+    assert_eq!(display_text(text, Some('*')), "**\n*");
+    assert_eq!(text_char_index(text, Some('*'), 1), 2);
+    assert_eq!(text_char_index(text, Some('*'), 3), 5);
+    assert_eq!(shown_char_index(text, Some('*'), 1), 0);
+    assert_eq!(shown_char_index(text, Some('*'), 2), 1);
+    assert_eq!(shown_char_index(text, Some('*'), 6), 4);
+    assert_eq!(text_char_index(text, None, 1), 1);
+}
+
+#[test]
+fn tab_indents_only_while_tab_is_unbound() {
+    use input::Key;
+    use input::keyboard::{CTRL, NO_MODIFIER, SHIFT};
+    let mut ui = ::UiBuilder::new().build();
+    assert_eq!(bound_command(Key::Tab, NO_MODIFIER, None, false, &ui), None);
+    assert_eq!(bound_command(Key::Tab, NO_MODIFIER, None, true, &ui), Some(Command::Indent));
+    assert_eq!(bound_command(Key::Tab, SHIFT, None, true, &ui), Some(Command::Outdent));
+    assert_eq!(bound_command(Key::Tab, CTRL, None, true, &ui), None);
+
+    // Bindings for `Tab` take precedence, whether given directly or via the `Theme`.
+    let key_bindings = KeyBindings::new().bind(Key::Tab, NO_MODIFIER, Command::SelectAll);
+    let command = bound_command(Key::Tab, SHIFT, Some(&key_bindings), true, &ui);
+    assert_eq!(command, None);
+    ui.theme.key_bindings = Some(key_bindings);
+    let command = bound_command(Key::Tab, NO_MODIFIER, None, true, &ui);
+    assert_eq!(command, Some(Command::SelectAll));
+}
@@ -10,6 +10,7 @@ use {
     Scalar,
     Widget,
 };
+use input;
 use widget;
 
 
@@ -127,11 +128,32 @@ impl<'a> Widget for Toggle<'a> {
         let widget::UpdateArgs { idx, state, style, rect, mut ui, .. } = args;
         let Toggle { value, enabled, maybe_label, .. } = self;
 
-        let times_clicked = TimesClicked {
-            state: value,
-            count: if enabled { ui.widget_input(idx).clicks().left().count() as u16 } else { 0 },
+        let times_clicked = {
+            let input = ui.widget_input(idx);
+            // A focused toggle may also be toggled via `Space` or `Return`.
+            let times_activated = input.presses().key()
+                .filter(|press| match press.key {
+                    input::Key::Space | input::Key::Return => true,
+                    _ => false,
+                })
+                .count();
+            let count = input.clicks().left().count() + times_activated;
+            TimesClicked {
+                state: value,
+                count: if enabled { count as u16 } else { 0 },
+            }
         };
 
+        // Stop the events that toggle the value from bubbling up to the toggle's parent.
+        if enabled {
+            let handled_events: Vec<_> = ui.widget_input(idx).events()
+                .filter(|event| widget::is_activation_event(event))
+                .collect();
+            for event in handled_events {
+                ui.set_event_handled(idx, event);
+            }
+        }
+
         // BorderedRectangle widget.
         let rectangle_idx = state.rectangle_idx.get(&mut ui);
         let dim = rect.dim();
@@ -0,0 +1,203 @@
+//! A floating popup that briefly describes some other widget.
+
+use {
+    Borderable,
+    Color,
+    Colorable,
+    Dimensions,
+    FontSize,
+    Point,
+    Positionable,
+    Scalar,
+    Sizeable,
+    Ui,
+    Widget,
+};
+use position::Dimension;
+use text;
+use utils;
+use widget;
+
+
+/// A floating popup displaying some short text, usually a description of the widget under the
+/// mouse.
+///
+/// The `Tooltip` sizes itself to fit its text. Most of the time there is no need to instantiate
+/// it directly, as any widget given a `Widget::tooltip` will have one displayed near the mouse
+/// once the mouse has rested over it for the `Theme`'s `hover_delay`.
+pub struct Tooltip<'a> {
+    common: widget::CommonBuilder,
+    text: &'a str,
+    style: Style,
+}
+
+widget_style!{
+    /// Unique graphical styling for the Tooltip.
+    style Style {
+        /// The color of the background behind the text.
+        - color: Color { theme.shape_color }
+        /// The width of the border surrounding the background.
+        - border: Scalar { theme.border_width }
+        /// The color of the border surrounding the background.
+        - border_color: Color { theme.border_color }
+        /// The color of the text.
+        - text_color: Color { theme.label_color }
+        /// The font size of the text.
+        - font_size: FontSize { theme.font_size_small }
+        /// The font used for the text.
+        ///
+        /// If `None`, the first font loaded into the `Ui` is used.
+        - font_id: Option<text::font::Id> { theme.font_id }
+        /// The length of the gap between the border and the edge of the text.
+        - padding: Scalar { 4.0 }
+    }
+}
+
+/// The state of the Tooltip.
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    rectangle_idx: widget::IndexSlot,
+    text_idx: widget::IndexSlot,
+}
+
+
+/// The distance between the mouse and the nearest corner of a tooltip displayed near it.
+pub const CURSOR_OFFSET: Scalar = 16.0;
+
+
+impl<'a> Tooltip<'a> {
+
+    /// Construct a new floating Tooltip displaying the given text.
+    pub fn new(text: &'a str) -> Self {
+        Tooltip {
+            common: widget::CommonBuilder::new(),
+            text: text,
+            style: Style::new(),
+        }.floating(true)
+    }
+
+    /// Specify the font used for the text.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.font_id = Some(Some(font_id));
+        self
+    }
+
+    builder_methods!{
+        pub text_color { style.text_color = Some(Color) }
+        pub font_size { style.font_size = Some(FontSize) }
+        pub padding { style.padding = Some(Scalar) }
+    }
+
+    // The `Text` widget used to display the text.
+    fn text_widget(&self, ui: &Ui) -> widget::Text<'a> {
+        widget::Text::new(self.text)
+            .no_line_wrap()
+            .font_size(self.style.font_size(&ui.theme))
+            .and_then(self.style.font_id(&ui.theme), |text, id| text.font_id(id))
+    }
+
+    // The space between the edge of the tooltip and the edge of its text.
+    fn margin(&self, ui: &Ui) -> Scalar {
+        self.style.padding(&ui.theme) + self.style.border(&ui.theme)
+    }
+
+}
+
+/// The centre of a tooltip with the given dimensions displayed near the mouse.
+///
+/// The tooltip is placed below and to the right of the mouse, unless doing so would place any part
+/// of it outside of a window with the given dimensions, in which case it is moved back inside.
+pub fn position_near_mouse(mouse_xy: Point, dim: Dimensions, window_dim: Dimensions) -> Point {
+    let half_w = dim[0] / 2.0;
+    let half_h = dim[1] / 2.0;
+    let x = mouse_xy[0] + CURSOR_OFFSET + half_w;
+    let y = mouse_xy[1] - CURSOR_OFFSET - half_h;
+    let (max_x, max_y) = (window_dim[0] / 2.0 - half_w, window_dim[1] / 2.0 - half_h);
+    // Clamping to the bottom and left edges last favours them when the tooltip is too large.
+    let x = utils::partial_max(utils::partial_min(x, max_x), -max_x);
+    let y = utils::partial_max(utils::partial_min(y, max_y), -max_y);
+    [x, y]
+}
+
+
+impl<'a> Widget for Tooltip<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn common(&self) -> &widget::CommonBuilder {
+        &self.common
+    }
+
+    fn common_mut(&mut self) -> &mut widget::CommonBuilder {
+        &mut self.common
+    }
+
+    fn init_state(&self) -> State {
+        State {
+            rectangle_idx: widget::IndexSlot::new(),
+            text_idx: widget::IndexSlot::new(),
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.style.clone()
+    }
+
+    /// By default, the tooltip is just wide enough to fit its text.
+    fn default_x_dimension(&self, ui: &Ui) -> Dimension {
+        let w = self.text_widget(ui).get_w(ui).unwrap_or(0.0);
+        Dimension::Absolute(w + self.margin(ui) * 2.0)
+    }
+
+    /// By default, the tooltip is just tall enough to fit its text.
+    fn default_y_dimension(&self, ui: &Ui) -> Dimension {
+        let h = self.text_widget(ui).get_h(ui).unwrap_or(0.0);
+        Dimension::Absolute(h + self.margin(ui) * 2.0)
+    }
+
+    /// Update the state of the Tooltip.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { idx, state, style, rect, mut ui, .. } = args;
+
+        let rectangle_idx = state.rectangle_idx.get(&mut ui);
+        widget::BorderedRectangle::new(rect.dim())
+            .xy(rect.xy())
+            .graphics_for(idx)
+            .parent(idx)
+            .color(style.color(ui.theme()))
+            .border(style.border(ui.theme()))
+            .border_color(style.border_color(ui.theme()))
+            .set(rectangle_idx, &mut ui);
+
+        let text_idx = state.text_idx.get(&mut ui);
+        let text_color = style.text_color(ui.theme());
+        self.text_widget(&ui)
+            .middle_of(rectangle_idx)
+            .graphics_for(idx)
+            .color(text_color)
+            .set(text_idx, &mut ui);
+    }
+
+}
+
+
+impl<'a> Colorable for Tooltip<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+impl<'a> Borderable for Tooltip<'a> {
+    builder_methods!{
+        border { style.border = Some(Scalar) }
+        border_color { style.border_color = Some(Color) }
+    }
+}
+
+
+#[test]
+fn tooltips_near_the_edge_of_the_window_remain_within_it() {
+    let window_dim = [200.0, 100.0];
+    let dim = [40.0, 20.0];
+    assert_eq!(position_near_mouse([0.0, 0.0], dim, window_dim), [36.0, -26.0]);
+    assert_eq!(position_near_mouse([90.0, -45.0], dim, window_dim), [80.0, -40.0]);
+}
@@ -10,9 +10,9 @@ use {
     Scalar,
     Widget,
 };
-use num::Float;
+use num::{Float, NumCast};
 use widget;
-use utils::{map_range, val_to_string};
+use utils::{clamp, map_range, percentage, val_to_string, value_from_perc};
 
 
 /// Used for displaying and controlling a 2D point on a cartesian plane within a given range.
@@ -52,6 +52,9 @@ widget_style!{
 /// The state of the XYPad.
 #[derive(Clone, Debug, PartialEq)]
 pub struct State {
+    /// The `x` and `y` values at the time the left mouse button was pressed, restored if the drag
+    /// is cancelled.
+    pre_drag: Option<(Scalar, Scalar)>,
     rectangle_idx: widget::IndexSlot,
     label_idx: widget::IndexSlot,
     h_line_idx: widget::IndexSlot,
@@ -100,6 +103,7 @@ impl<'a, X, Y> Widget for XYPad<'a, X, Y>
 
     fn init_state(&self) -> Self::State {
         State {
+            pre_drag: None,
             rectangle_idx: widget::IndexSlot::new(),
             label_idx: widget::IndexSlot::new(),
             h_line_idx: widget::IndexSlot::new(),
@@ -140,6 +144,55 @@ impl<'a, X, Y> Widget for XYPad<'a, X, Y>
             }
         }
 
+        // Remember the point while the left mouse button is down so that it may be restored if the
+        // drag is cancelled.
+        let mut pre_drag = state.pre_drag;
+        for widget_event in ui.widget_input(idx).events() {
+            use event;
+            use input::MouseButton;
+
+            match widget_event {
+                event::Widget::Press(press) => {
+                    if let event::Button::Mouse(MouseButton::Left, _) = press.button {
+                        pre_drag = Some((x.to_f64().unwrap(), y.to_f64().unwrap()));
+                    }
+                },
+                event::Widget::Release(release) => {
+                    if let event::Button::Mouse(MouseButton::Left, _) = release.button {
+                        pre_drag = None;
+                    }
+                },
+                event::Widget::DragCancelled(drag) if drag.button == MouseButton::Left => {
+                    if let Some((pre_x, pre_y)) = pre_drag.take() {
+                        new_x = NumCast::from(pre_x).unwrap();
+                        new_y = NumCast::from(pre_y).unwrap();
+                    }
+                },
+                _ => (),
+            }
+        }
+        if pre_drag != state.pre_drag {
+            state.update(|state| state.pre_drag = pre_drag);
+        }
+
+        // Stop the mouse events that move the point from bubbling up to the pad's parent.
+        let handled_events: Vec<_> = ui.widget_input(idx).events()
+            .filter(|event| widget::is_slide_event(event))
+            .collect();
+        for event in handled_events {
+            ui.set_event_handled(idx, event);
+        }
+
+        // Adjust the point with any controller stick pushed while the pad has keyboard focus.
+        fn adjust_value<T: Float>(value: T, min: T, max: T, amount: Scalar) -> T {
+            let perc = clamp(percentage(value, min, max) + amount as f32, 0.0, 1.0);
+            value_from_perc(perc, min, max)
+        }
+        for adjust in ui.widget_input(idx).adjustments() {
+            new_x = adjust_value(new_x, min_x, max_x, adjust.amount[0]);
+            new_y = adjust_value(new_y, min_y, max_y, adjust.amount[1]);
+        }
+
         // If the value across either axis has changed, produce an event.
         let event = if x != new_x || y != new_y {
             Some((new_x, new_y))